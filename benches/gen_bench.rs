@@ -0,0 +1,139 @@
+//! Benchmarks of the generators, bounded sampling, and float conversion.
+//!
+//! Run with `cargo bench`. Each generator bench measures 1000 `next_u32`
+//! calls, so throughput is reported per output.
+
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use randomize::{
+  formulas::ieee754_random_f32, BoundedRandU16, BoundedRandU32, Gen32, MCG32, PCG32, PCG32K,
+  PCG64DXSM,
+};
+
+const OUTPUTS: u64 = 1000;
+
+fn run<G: Gen32>(gen: &mut G) -> u32 {
+  let mut acc = 0_u32;
+  for _ in 0..OUTPUTS {
+    acc ^= gen.next_u32();
+  }
+  acc
+}
+
+fn generators(c: &mut Criterion) {
+  let mut group = c.benchmark_group("next_u32");
+  group.throughput(Throughput::Elements(OUTPUTS));
+  group.bench_function("PCG32", |b| {
+    let mut g = PCG32::seed(1, 1);
+    b.iter(|| run(&mut g))
+  });
+  group.bench_function("MCG32", |b| {
+    let mut g = MCG32::seed(1);
+    b.iter(|| run(&mut g))
+  });
+  group.bench_function("PCG64DXSM", |b| {
+    let mut g = PCG64DXSM::seed(1, 1);
+    b.iter(|| run(&mut g))
+  });
+  group.finish();
+
+  // A power of two `K` lets the extension index be a bit mask instead of a
+  // remainder, so the sizes here alternate between the two kinds.
+  let mut group = c.benchmark_group("PCG32K");
+  group.throughput(Throughput::Elements(OUTPUTS));
+  macro_rules! bench_k {
+    ($($k:literal),*) => {$(
+      group.bench_function(BenchmarkId::from_parameter($k), |b| {
+        let mut g = PCG32K::<$k>::seed(1, [1; $k]);
+        b.iter(|| run(&mut g))
+      });
+    )*};
+  }
+  bench_k!(1, 2, 3, 4, 7, 8, 16, 31, 32, 64, 100, 128, 256, 1000, 1024);
+  group.finish();
+}
+
+fn bounded(c: &mut Criterion) {
+  // The rejection rate of a count is `(2**bits % count) / 2**bits`. A count
+  // just over half the range rejects almost half of all inputs, and a power of
+  // two never rejects.
+  let mut group = c.benchmark_group("bounded");
+  group.throughput(Throughput::Elements(OUTPUTS));
+  for count in [6_u16, 1 << 15, (1 << 15) + 1] {
+    let bounded = BoundedRandU16::new(count);
+    group.bench_function(BenchmarkId::new("U16", count), |b| {
+      let mut g = PCG32::seed(1, 1);
+      b.iter(|| {
+        let mut acc = 0;
+        for _ in 0..OUTPUTS {
+          acc ^= bounded.sample(|| (g.next_u32() >> 16) as u16);
+        }
+        acc
+      })
+    });
+  }
+  for count in [6_u32, 1 << 15, (1 << 15) + 1, (1 << 31) + 1] {
+    let bounded = BoundedRandU32::new(count);
+    group.bench_function(BenchmarkId::new("U32", count), |b| {
+      let mut g = PCG32::seed(1, 1);
+      b.iter(|| {
+        let mut acc = 0;
+        for _ in 0..OUTPUTS {
+          acc ^= bounded.sample(|| g.next_u32());
+        }
+        acc
+      })
+    });
+  }
+  group.finish();
+}
+
+fn floats(c: &mut Criterion) {
+  let mut group = c.benchmark_group("f32");
+  group.throughput(Throughput::Elements(OUTPUTS));
+  group.bench_function("ieee754_random_f32", |b| {
+    let mut g = PCG32::seed(1, 1);
+    b.iter(|| {
+      let mut acc = 0.0;
+      for _ in 0..OUTPUTS {
+        acc += ieee754_random_f32(|| g.next_u32(), false);
+      }
+      acc
+    })
+  });
+  group.bench_function("multiply", |b| {
+    // The common conversion, which only ever gives multiples of `2**-24`.
+    let mut g = PCG32::seed(1, 1);
+    b.iter(|| {
+      let mut acc = 0.0;
+      for _ in 0..OUTPUTS {
+        acc += (g.next_u32() >> 8) as f32 * (1.0 / (1 << 24) as f32);
+      }
+      acc
+    })
+  });
+  group.finish();
+}
+
+fn dice(c: &mut Criterion) {
+  let mut group = c.benchmark_group("dice");
+  group.throughput(Throughput::Elements(OUTPUTS));
+  macro_rules! bench_die {
+    ($($die:ident),*) => {$(
+      group.bench_function(stringify!($die), |b| {
+        let mut g = PCG32::seed(1, 1);
+        b.iter(|| {
+          let mut acc = 0;
+          for _ in 0..OUTPUTS {
+            acc += g.$die();
+          }
+          black_box(acc)
+        })
+      });
+    )*};
+  }
+  bench_die!(d4, d6, d8, d10, d12, d20);
+  group.finish();
+}
+
+criterion_group!(benches, generators, bounded, floats, dice);
+criterion_main!(benches);
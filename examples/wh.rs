@@ -1,5 +1,6 @@
 #![allow(clippy::if_same_then_else)]
 #![allow(clippy::collapsible_if)]
+#![allow(clippy::collapsible_match)]
 
 use randomize::{Gen32, PCG32K};
 
@@ -0,0 +1,19 @@
+//! A dice simulation like `wh.rs`, split across threads.
+//!
+//! Each trial gets its own substream, so the result for a given seed is
+//! exactly the same no matter how many threads are used.
+
+use randomize::{par_trials, Gen32};
+
+fn main() {
+  let seed = std::env::args().nth(1).and_then(|s| s.parse().ok()).unwrap_or(0);
+  let trials = 100000000;
+
+  // One combi-weapon shot: hit on a 4+ then wound on a 4+, with a re-roll
+  // allowed for each.
+  let total: u64 = par_trials(seed, trials, |gen, _| {
+    let hit = gen.d6() >= 4 || gen.d6() >= 4;
+    (hit && (gen.d6() >= 4 || gen.d6() >= 4)) as u64
+  });
+  println!("{}", (total as f64) / (trials as f64));
+}
@@ -0,0 +1,82 @@
+#![forbid(unsafe_code)]
+
+//! Derive macro for the `Random` trait of the `randomize` crate.
+//!
+//! Use this through the `derive` cargo feature of `randomize` rather than
+//! depending on it directly.
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::{parse_macro_input, parse_quote, Data, DeriveInput, Fields};
+
+/// Derives `randomize::Random`.
+///
+/// * A struct generates each of its fields in order.
+/// * An enum picks one of its variants uniformly, then generates the fields of
+///   that variant in order.
+///
+/// Every type parameter gets a `Random` bound.
+#[proc_macro_derive(Random)]
+pub fn derive_random(input: TokenStream) -> TokenStream {
+  let mut input = parse_macro_input!(input as DeriveInput);
+  for param in input.generics.type_params_mut() {
+    param.bounds.push(parse_quote!(::randomize::Random));
+  }
+  let name = &input.ident;
+  let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+  let body = match &input.data {
+    Data::Struct(s) => construct(quote!(Self), &s.fields),
+    Data::Enum(e) => {
+      if e.variants.is_empty() {
+        return syn::Error::new_spanned(name, "can't derive `Random` for an enum with no variants")
+          .to_compile_error()
+          .into();
+      }
+      let count = e.variants.len() as u32;
+      let arms = e.variants.iter().enumerate().map(|(i, v)| {
+        let i = i as u32;
+        let ident = &v.ident;
+        let value = construct(quote!(Self::#ident), &v.fields);
+        quote!(#i => #value,)
+      });
+      quote! {
+        match ::randomize::Gen32::sample_u32(g, ::randomize::BoundedRandU32::new(#count)) {
+          #(#arms)*
+          _ => unreachable!(),
+        }
+      }
+    }
+    Data::Union(u) => {
+      return syn::Error::new_spanned(u.union_token, "can't derive `Random` for a union")
+        .to_compile_error()
+        .into();
+    }
+  };
+
+  quote! {
+    impl #impl_generics ::randomize::Random for #name #ty_generics #where_clause {
+      #[inline]
+      fn random<G: ::randomize::Gen32>(g: &mut G) -> Self {
+        #body
+      }
+    }
+  }
+  .into()
+}
+
+/// An expression that builds `path` with every field randomly generated.
+fn construct(path: TokenStream2, fields: &Fields) -> TokenStream2 {
+  match fields {
+    Fields::Named(named) => {
+      let names = named.named.iter().map(|f| &f.ident);
+      quote!(#path { #(#names: ::randomize::Random::random(g),)* })
+    }
+    Fields::Unnamed(unnamed) => {
+      let values = unnamed.unnamed.iter().map(|_| quote!(::randomize::Random::random(g)));
+      quote!(#path ( #(#values,)* ))
+    }
+    Fields::Unit => path,
+  }
+}
@@ -0,0 +1,116 @@
+use crate::{BoundedRandU32, Error};
+
+/// A set of ASCII symbols to pick random characters from.
+///
+/// Each symbol is picked with equal chance, using a [BoundedRandU32] so that
+/// there's no modulo bias.
+///
+/// ```
+/// # use randomize::{Alphabet, Gen32, PCG32};
+/// let mut g = PCG32::seed(1, 1);
+/// let mut code = [0_u8; 6];
+/// g.fill_from_alphabet(&Alphabet::CROCKFORD_BASE32, &mut code);
+/// assert!(code.iter().all(|b| b"0123456789ABCDEFGHJKMNPQRSTVWXYZ".contains(b)));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Alphabet<'a> {
+  symbols: &'a [u8],
+  bounded: BoundedRandU32,
+}
+impl<'a> Alphabet<'a> {
+  /// `0-9`, `A-Z`, and `a-z`.
+  pub const ALPHANUMERIC: Alphabet<'static> =
+    Alphabet::new(b"0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz");
+
+  /// `0-9` and `a-f`.
+  pub const HEX: Alphabet<'static> = Alphabet::new(b"0123456789abcdef");
+
+  /// Crockford's base32, which leaves out `I`, `L`, `O`, and `U` so that codes
+  /// are easy to read aloud and hard to misread.
+  pub const CROCKFORD_BASE32: Alphabet<'static> =
+    Alphabet::new(b"0123456789ABCDEFGHJKMNPQRSTVWXYZ");
+
+  /// Makes an alphabet of the symbols given.
+  ///
+  /// Repeating a symbol makes it proportionally more likely.
+  ///
+  /// ## Panics
+  /// * If the symbols are empty, not all ASCII, or more than `u32::MAX`.
+  #[inline]
+  #[must_use]
+  pub const fn new(symbols: &'a [u8]) -> Self {
+    match Self::try_new(symbols) {
+      Ok(alphabet) => alphabet,
+      Err(_) => panic!("an alphabet must be 1 to u32::MAX ASCII symbols"),
+    }
+  }
+
+  /// Makes an alphabet of the symbols given.
+  ///
+  /// ## Failure
+  /// * If the symbols are empty this gives [Error::InvalidRange].
+  /// * If the symbols are not all ASCII, or there are more than `u32::MAX`,
+  ///   this gives [Error::InvalidParameter].
+  #[inline]
+  pub const fn try_new(symbols: &'a [u8]) -> Result<Self, Error> {
+    if !symbols.is_ascii() {
+      return Err(Error::InvalidParameter("alphabet symbols must be ASCII"));
+    }
+    if symbols.len() > u32::MAX as usize {
+      return Err(Error::InvalidParameter("alphabet has too many symbols"));
+    }
+    match BoundedRandU32::try_new(symbols.len() as u32) {
+      Ok(bounded) => Ok(Self { symbols, bounded }),
+      Err(e) => Err(e),
+    }
+  }
+
+  /// The symbols of this alphabet.
+  #[inline]
+  #[must_use]
+  pub const fn symbols(&self) -> &'a [u8] {
+    self.symbols
+  }
+
+  /// The range used to pick the index of a symbol.
+  #[inline]
+  #[must_use]
+  pub const fn bounded(&self) -> BoundedRandU32 {
+    self.bounded
+  }
+
+  /// Picks a symbol, using a generator function.
+  #[inline]
+  pub fn sample<F: FnMut() -> u32>(&self, f: F) -> u8 {
+    self.symbols[self.bounded.sample(f) as usize]
+  }
+}
+
+#[test]
+fn test_alphabet() {
+  use crate::{testing::ScriptedGen, Gen32};
+  assert_eq!(Alphabet::try_new(b""), Err(Error::InvalidRange));
+  assert!(matches!(Alphabet::try_new("é".as_bytes()), Err(Error::InvalidParameter(_))));
+
+  let b = BoundedRandU32::new(16);
+  let script = [b.raw_for(0), b.raw_for(10), b.raw_for(15)];
+  let mut g = ScriptedGen::new(&script);
+  let mut out = [0; 3];
+  g.fill_from_alphabet(&Alphabet::HEX, &mut out);
+  assert_eq!(&out, b"0af");
+}
+
+#[test]
+fn test_char_helpers() {
+  use crate::{testing::ScriptedGen, Gen32};
+  let b = BoundedRandU32::new(3);
+  let script = [b.raw_for(2), b.raw_for(0)];
+  let mut g = ScriptedGen::new(&script);
+  assert_eq!(g.next_char_in(&['a', 'ß', '🎲']), '🎲');
+  assert_eq!(g.next_char_in(&['a', 'ß', '🎲']), 'a');
+  #[cfg(feature = "alloc")]
+  {
+    let mut g = ScriptedGen::cycling(&script);
+    assert_eq!(g.string_from_alphabet(&Alphabet::new(b"xyz"), 4), "zxzx");
+  }
+}
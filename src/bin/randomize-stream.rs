@@ -0,0 +1,316 @@
+//! Writes raw generator output to stdout, for piping into test batteries.
+//!
+//! ```sh
+//! randomize-stream --gen pcg32 --seed 5 | RNG_test stdin32
+//! randomize-stream --gen pcg64dxsm --format stdin64 | RNG_test stdin64
+//! randomize-stream --gen pcg32k16 --format u32be --words 100000000 > out.bin
+//! ```
+//!
+//! The `u32be` format matches what TestU01's `ufile_CreateReadBin` expects.
+
+use randomize::{
+  formulas::{lcg64_jump, lcg64_step, PCG_MUL_64},
+  Philox4x32, MCG32, MCG64, PCG32, PCG32K, PCG64DXSM,
+};
+use std::{
+  io::{self, BufWriter, ErrorKind, Write},
+  process::ExitCode,
+};
+
+const USAGE: &str = "\
+Usage: randomize-stream [OPTIONS]
+
+Writes raw generator output to stdout until the output is closed.
+
+Options:
+  --gen <NAME>      pcg32 (default), pcg32k<N>, pcg64dxsm, mcg32, mcg64, philox4x32
+                    (N is one of 1, 2, 4, 8, 16, 32, 64, 128, 256, 512, 1024)
+  --seed <N>        seed value (default 0)
+  --stream <N>      stream selection (default 0): the increment of pcg32 and
+                    pcg64dxsm, or the seed of the extension array of pcg32k<N>
+  --jump <N>        advance the generator by N steps before writing
+  --format <FMT>    stdin32 (default), stdin64, or u32be
+  --words <N>       stop after N output words (default: never)
+  --help            print this message
+
+Numbers can be decimal or 0x-prefixed hex.";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Format {
+  /// Little-endian `u32` values, for PractRand's `stdin32`.
+  Stdin32,
+  /// Little-endian `u64` values, for PractRand's `stdin64`.
+  Stdin64,
+  /// Big-endian `u32` values, for TestU01's binary file reader.
+  U32Be,
+}
+
+#[derive(Debug)]
+struct Options {
+  gen: String,
+  seed: u128,
+  stream: u128,
+  jump: u128,
+  format: Format,
+  words: Option<u64>,
+}
+
+fn main() -> ExitCode {
+  let opts = match parse_args(std::env::args().skip(1)) {
+    Ok(Some(opts)) => opts,
+    Ok(None) => {
+      println!("{USAGE}");
+      return ExitCode::SUCCESS;
+    }
+    Err(msg) => {
+      eprintln!("error: {msg}\n\n{USAGE}");
+      return ExitCode::FAILURE;
+    }
+  };
+  let result = match make_source(&opts) {
+    Ok(source) => write_stream(source, opts.format, opts.words),
+    Err(msg) => {
+      eprintln!("error: {msg}");
+      return ExitCode::FAILURE;
+    }
+  };
+  match result {
+    // the reader closing the pipe is how a test battery says it's done.
+    Err(e) if e.kind() != ErrorKind::BrokenPipe => {
+      eprintln!("error: {e}");
+      ExitCode::FAILURE
+    }
+    _ => ExitCode::SUCCESS,
+  }
+}
+
+fn parse_args(mut args: impl Iterator<Item = String>) -> Result<Option<Options>, String> {
+  let mut opts = Options {
+    gen: String::from("pcg32"),
+    seed: 0,
+    stream: 0,
+    jump: 0,
+    format: Format::Stdin32,
+    words: None,
+  };
+  while let Some(arg) = args.next() {
+    if arg == "--help" || arg == "-h" {
+      return Ok(None);
+    }
+    let value = args.next().ok_or_else(|| format!("missing value for `{arg}`"))?;
+    match arg.as_str() {
+      "--gen" => opts.gen = value.to_ascii_lowercase(),
+      "--seed" => opts.seed = parse_num(&value)?,
+      "--stream" => opts.stream = parse_num(&value)?,
+      "--jump" => opts.jump = parse_num(&value)?,
+      "--format" => {
+        opts.format = match value.as_str() {
+          "stdin32" => Format::Stdin32,
+          "stdin64" => Format::Stdin64,
+          "u32be" => Format::U32Be,
+          _ => return Err(format!("unknown format `{value}`")),
+        }
+      }
+      "--words" => {
+        let words = parse_num(&value)?;
+        opts.words = Some(u64::try_from(words).map_err(|_| "--words is too large")?);
+      }
+      _ => return Err(format!("unknown option `{arg}`")),
+    }
+  }
+  Ok(Some(opts))
+}
+
+fn parse_num(s: &str) -> Result<u128, String> {
+  let s = s.replace('_', "");
+  let parsed = match s.strip_prefix("0x").or_else(|| s.strip_prefix("0X")) {
+    Some(hex) => u128::from_str_radix(hex, 16),
+    None => s.parse(),
+  };
+  parsed.map_err(|_| format!("invalid number `{s}`"))
+}
+
+fn narrow(value: u128, what: &str, gen: &str) -> Result<u64, String> {
+  u64::try_from(value).map_err(|_| format!("{what} is too large for {gen}"))
+}
+
+/// A generator that can give either 32 or 64 bits per step.
+enum Source {
+  Pcg32(PCG32),
+  Pcg64(PCG64DXSM),
+  Mcg32(MCG32),
+  Mcg64(MCG64),
+  Philox(Philox4x32),
+  Dyn(Box<dyn FnMut() -> u32>),
+}
+impl Source {
+  fn next_u32(&mut self) -> u32 {
+    match self {
+      Self::Pcg32(g) => g.next_u32(),
+      Self::Pcg64(g) => g.next_u32(),
+      Self::Mcg32(g) => g.next_u32(),
+      Self::Mcg64(g) => g.next_u32(),
+      Self::Philox(g) => g.next_u32(),
+      Self::Dyn(f) => f(),
+    }
+  }
+
+  fn next_u64(&mut self) -> u64 {
+    match self {
+      Self::Pcg64(g) => g.next_u64(),
+      Self::Mcg64(g) => g.next_u64(),
+      _ => {
+        let lo = u64::from(self.next_u32());
+        let hi = u64::from(self.next_u32());
+        lo | (hi << 32)
+      }
+    }
+  }
+}
+
+fn make_source(opts: &Options) -> Result<Source, String> {
+  let gen = opts.gen.as_str();
+  let no_stream = || {
+    if opts.stream == 0 {
+      Ok(())
+    } else {
+      Err(format!("{gen} doesn't have streams"))
+    }
+  };
+  let source = match gen {
+    "pcg32" => {
+      let mut g = PCG32::seed(narrow(opts.seed, "seed", gen)?, narrow(opts.stream, "stream", gen)?);
+      g.jump(narrow(opts.jump, "jump", gen)?);
+      Source::Pcg32(g)
+    }
+    "pcg64dxsm" => {
+      let mut g = PCG64DXSM::seed(opts.seed, opts.stream);
+      g.jump(opts.jump);
+      Source::Pcg64(g)
+    }
+    "mcg32" => {
+      no_stream()?;
+      let mut g = MCG32::seed(narrow(opts.seed, "seed", gen)?);
+      g.jump(narrow(opts.jump, "jump", gen)?);
+      Source::Mcg32(g)
+    }
+    "mcg64" => {
+      no_stream()?;
+      let mut g = MCG64::seed(opts.seed);
+      g.jump(opts.jump);
+      Source::Mcg64(g)
+    }
+    "philox4x32" => {
+      no_stream()?;
+      let seed = narrow(opts.seed, "seed", gen)?;
+      let mut g = Philox4x32::new([seed as u32, (seed >> 32) as u32]);
+      g.jump(opts.jump);
+      Source::Philox(g)
+    }
+    _ => match gen.strip_prefix("pcg32k").and_then(|k| k.parse::<usize>().ok()) {
+      Some(k) => {
+        let seed = narrow(opts.seed, "seed", gen)?;
+        let stream = narrow(opts.stream, "stream", gen)?;
+        let jump = narrow(opts.jump, "jump", gen)?;
+        pcg32k_source(k, seed, stream, jump)
+          .ok_or_else(|| format!("unsupported extension size {k}"))?
+      }
+      None => return Err(format!("unknown generator `{gen}`")),
+    },
+  };
+  Ok(source)
+}
+
+fn pcg32k_source(k: usize, seed: u64, stream: u64, jump: u64) -> Option<Source> {
+  fn boxed<const K: usize>(seed: u64, stream: u64, jump: u64) -> Source {
+    // The extension array is filled from a PCG32 on the chosen stream.
+    let mut ext_gen = PCG32::seed(seed, stream);
+    let mut g = PCG32K::<K>::seed(seed, core::array::from_fn(|_| ext_gen.next_u32()));
+    pcg32k_jump(&mut g, jump);
+    Source::Dyn(Box::new(move || g.next_u32()))
+  }
+  Some(match k {
+    1 => boxed::<1>(seed, stream, jump),
+    2 => boxed::<2>(seed, stream, jump),
+    4 => boxed::<4>(seed, stream, jump),
+    8 => boxed::<8>(seed, stream, jump),
+    16 => boxed::<16>(seed, stream, jump),
+    32 => boxed::<32>(seed, stream, jump),
+    64 => boxed::<64>(seed, stream, jump),
+    128 => boxed::<128>(seed, stream, jump),
+    256 => boxed::<256>(seed, stream, jump),
+    512 => boxed::<512>(seed, stream, jump),
+    1024 => boxed::<1024>(seed, stream, jump),
+    _ => return None,
+  })
+}
+
+/// Jumps a `PCG32K` to where `delta` calls to `next_u32` would leave it.
+///
+/// The base LCG has a full period, so its state passes 0 at most once during
+/// the jump. If it does, the extension array gets the same carrying `+1` that
+/// `next_u32` would have given it.
+fn pcg32k_jump<const K: usize>(g: &mut PCG32K<K>, delta: u64) {
+  if lcg64_distance(PCG_MUL_64, 1, g.state, 0) < delta {
+    for ext in &mut g.ext {
+      let (new_ext, carry) = ext.overflowing_add(1);
+      *ext = new_ext;
+      if !carry {
+        break;
+      }
+    }
+  }
+  g.state = lcg64_jump(PCG_MUL_64, 1, g.state, delta);
+}
+
+/// How many steps it takes the LCG to get from `state` to `target`.
+///
+/// This works one bit at a time: the low `n` bits of the state only depend on
+/// the low `n` bits of the previous state, and the step that fixes bit `n`
+/// without disturbing the lower bits is a jump of `2**n` steps.
+fn lcg64_distance(mut mul: u64, mut add: u64, mut state: u64, target: u64) -> u64 {
+  let mut bit = 1_u64;
+  let mut distance = 0;
+  while state != target {
+    if (state ^ target) & bit != 0 {
+      state = lcg64_step(mul, add, state);
+      distance |= bit;
+    }
+    add = mul.wrapping_add(1).wrapping_mul(add);
+    mul = mul.wrapping_mul(mul);
+    bit <<= 1;
+  }
+  distance
+}
+
+fn write_stream(mut source: Source, format: Format, words: Option<u64>) -> io::Result<()> {
+  let stdout = io::stdout();
+  let mut out = BufWriter::with_capacity(1 << 16, stdout.lock());
+  let mut written = 0_u64;
+  while words.is_none_or(|w| written < w) {
+    match format {
+      Format::Stdin32 => out.write_all(&source.next_u32().to_le_bytes())?,
+      Format::Stdin64 => out.write_all(&source.next_u64().to_le_bytes())?,
+      Format::U32Be => out.write_all(&source.next_u32().to_be_bytes())?,
+    }
+    written += 1;
+  }
+  out.flush()
+}
+
+#[test]
+fn test_pcg32k_jump() {
+  // Start a few steps before the state passes 0, so the jump has to carry
+  // into the extension array.
+  let start = lcg64_jump(PCG_MUL_64, 1, 0, 5_u64.wrapping_neg());
+  assert_eq!(lcg64_distance(PCG_MUL_64, 1, start, 0), 5);
+  for delta in [0, 3, 5, 6, 1000] {
+    let mut stepped = PCG32K::<2>::new(start, [u32::MAX, 7]);
+    for _ in 0..delta {
+      stepped.next_u32();
+    }
+    let mut jumped = PCG32K::<2>::new(start, [u32::MAX, 7]);
+    pcg32k_jump(&mut jumped, delta);
+    assert_eq!((jumped.state, jumped.ext), (stepped.state, stepped.ext), "delta {delta}");
+  }
+}
@@ -1,5 +1,7 @@
 //! Types for bounded randomization.
 
+use crate::Error;
+
 /// Allows sampling a `u32` number in `0 .. N`.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub struct BoundedRandU32 {
@@ -30,20 +32,21 @@ impl BoundedRandU32 {
   /// If the count is 0.
   #[inline]
   pub const fn new(count: u32) -> Self {
+    assert!(count > 0, "the count must be non-zero");
     let threshold = count.wrapping_neg() % count;
     Self { count, threshold }
   }
 
-  /// Constructs a new value, or `None` on failure.
+  /// Constructs a new value.
   ///
   /// ## Failure
-  /// If the count is 0.
+  /// * If the count is 0 this gives [Error::InvalidRange].
   #[inline]
-  pub const fn try_new(count: u32) -> Option<Self> {
+  pub const fn try_new(count: u32) -> Result<Self, Error> {
     if count > 0 {
-      Some(Self::new(count))
+      Ok(Self::new(count))
     } else {
-      None
+      Err(Error::InvalidRange)
     }
   }
 
@@ -69,6 +72,21 @@ impl BoundedRandU32 {
     }
   }
 
+  /// Gives a raw value that [`place_in_range`](Self::place_in_range) maps to
+  /// the value given.
+  ///
+  /// This is the largest raw value that gives that output, which is never
+  /// rejected. It's intended for scripting generator output in tests.
+  ///
+  /// ## Panics
+  /// * If the value is not less than the count.
+  #[inline]
+  #[must_use]
+  pub const fn raw_for(self, value: u32) -> u32 {
+    assert!(value < self.count, "the value must be less than the count");
+    ((((value as u128 + 1) << 32) - 1) / self.count as u128) as u32
+  }
+
   /// Given a generator function, call it until
   /// [`place_in_range`](Self::place_in_range) succeeds.
   #[inline]
@@ -115,20 +133,21 @@ impl BoundedRandU16 {
   /// If the count is 0.
   #[inline]
   pub const fn new(count: u16) -> Self {
+    assert!(count > 0, "the count must be non-zero");
     let threshold = count.wrapping_neg() % count;
     Self { count, threshold }
   }
 
-  /// Constructs a new value, or `None` on failure.
+  /// Constructs a new value.
   ///
   /// ## Failure
-  /// If the count is 0.
+  /// * If the count is 0 this gives [Error::InvalidRange].
   #[inline]
-  pub const fn try_new(count: u16) -> Option<Self> {
+  pub const fn try_new(count: u16) -> Result<Self, Error> {
     if count > 0 {
-      Some(Self::new(count))
+      Ok(Self::new(count))
     } else {
-      None
+      Err(Error::InvalidRange)
     }
   }
 
@@ -154,6 +173,21 @@ impl BoundedRandU16 {
     }
   }
 
+  /// Gives a raw value that [`place_in_range`](Self::place_in_range) maps to
+  /// the value given.
+  ///
+  /// This is the largest raw value that gives that output, which is never
+  /// rejected. It's intended for scripting generator output in tests.
+  ///
+  /// ## Panics
+  /// * If the value is not less than the count.
+  #[inline]
+  #[must_use]
+  pub const fn raw_for(self, value: u16) -> u16 {
+    assert!(value < self.count, "the value must be less than the count");
+    ((((value as u64 + 1) << 16) - 1) / self.count as u64) as u16
+  }
+
   /// Given a generator function, call it until
   /// [`place_in_range`](Self::place_in_range) succeeds.
   #[inline]
@@ -165,3 +199,32 @@ impl BoundedRandU16 {
     }
   }
 }
+
+#[test]
+fn test_try_new_zero() {
+  assert_eq!(BoundedRandU32::try_new(0), Err(Error::InvalidRange));
+  assert_eq!(BoundedRandU16::try_new(0), Err(Error::InvalidRange));
+  assert_eq!(BoundedRandU32::try_new(6), Ok(BoundedRandU32::_6));
+}
+
+#[test]
+#[should_panic = "the count must be non-zero"]
+fn test_new_zero_panics() {
+  let _ = BoundedRandU32::new(core::hint::black_box(0));
+}
+
+#[test]
+fn test_raw_for() {
+  for count in [1, 2, 3, 6, 7, 20, 1000, u16::MAX] {
+    let b = BoundedRandU16::new(count);
+    for value in [0, count / 2, count - 1] {
+      assert_eq!(b.place_in_range(b.raw_for(value)), Some(value), "{count} {value}");
+    }
+  }
+  for count in [1, 6, 1 << 31, (1 << 31) + 1, u32::MAX - 1, u32::MAX] {
+    let b = BoundedRandU32::new(count);
+    for value in [0, count / 2, count - 1] {
+      assert_eq!(b.place_in_range(b.raw_for(value)), Some(value), "{count} {value}");
+    }
+  }
+}
@@ -0,0 +1,82 @@
+use crate::StateError;
+use core::{fmt, num::NonZeroU32};
+
+/// The error type for the crate's fallible operations.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum Error {
+  /// A range was empty, such as a bounded range with a count of 0.
+  InvalidRange,
+  /// A parameter was outside of the values that the operation supports.
+  InvalidParameter(&'static str),
+  /// The system entropy source failed.
+  ///
+  /// The value is the error code that the entropy source reported.
+  Seeding(NonZeroU32),
+  /// Generator state bytes couldn't be encoded or decoded.
+  BadState(StateError),
+  /// A [Replayer](crate::Replayer) was used for a different number of draws
+  /// than its log holds.
+  ReplayDiverged {
+    /// The number of values in the log.
+    recorded: usize,
+    /// The number of values drawn.
+    drawn: usize,
+  },
+}
+
+impl fmt::Display for Error {
+  #[inline]
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      Self::InvalidRange => write!(f, "the range is empty"),
+      Self::InvalidParameter(msg) => write!(f, "invalid parameter: {msg}"),
+      Self::Seeding(code) => write!(f, "the entropy source failed with code {code}"),
+      Self::BadState(e) => write!(f, "bad generator state: {e}"),
+      Self::ReplayDiverged { recorded, drawn } => {
+        write!(f, "replay diverged: {recorded} values were recorded, but {drawn} were drawn")
+      }
+    }
+  }
+}
+
+impl core::error::Error for Error {
+  #[inline]
+  fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+    match self {
+      Self::BadState(e) => Some(e),
+      _ => None,
+    }
+  }
+}
+
+impl From<StateError> for Error {
+  #[inline]
+  fn from(e: StateError) -> Self {
+    Self::BadState(e)
+  }
+}
+
+#[cfg(feature = "getrandom")]
+#[cfg_attr(docsrs, doc(cfg(feature = "getrandom")))]
+impl From<getrandom::Error> for Error {
+  #[inline]
+  fn from(e: getrandom::Error) -> Self {
+    Self::Seeding(e.code())
+  }
+}
+
+#[test]
+fn test_error_conversions() {
+  use core::error::Error as _;
+
+  let e = Error::from(StateError::BadMagic);
+  assert_eq!(e, Error::BadState(StateError::BadMagic));
+  assert!(e.source().is_some());
+  assert!(Error::InvalidRange.source().is_none());
+  #[cfg(feature = "getrandom")]
+  {
+    let g = getrandom::Error::UNSUPPORTED;
+    assert_eq!(Error::from(g), Error::Seeding(g.code()));
+  }
+}
@@ -1,8 +1,57 @@
 //! Base formulas used elsewhere in the crate.
 
+/// This is the suggested multiplier for a PCG with 32 bits of state.
+pub const PCG_MUL_32: u32 = 747796405;
+
 /// This is the suggested multiplier for a PCG with 64 bits of state.
 pub const PCG_MUL_64: u64 = 6364136223846793005;
 
+/// This is the suggested multiplier for a PCG with 128 bits of state.
+pub const PCG_MUL_128: u128 = 0x2360ed051fc65da44385df649fccf645;
+
+/// This is the default increment for a PCG with 32 bits of state.
+pub const PCG_INC_32: u32 = 2891336453;
+
+/// This is the default increment for a PCG with 64 bits of state.
+pub const PCG_INC_64: u64 = 1442695040888963407;
+
+/// This is the default increment for a PCG with 128 bits of state.
+pub const PCG_INC_128: u128 = 0x5851f42d4c957f2d14057b7ef767814f;
+
+/// This is the "cheap multiplier" for a PCG with 128 bits of state.
+///
+/// Using a 64-bit multiplier with a 128-bit state makes each step
+/// significantly cheaper, and the [DXSM](dxsm_u128_to_u64) output function
+/// makes up for the weaker state mixing. This is the multiplier used by
+/// NumPy's `PCG64DXSM`.
+pub const PCG_CHEAP_MUL_128: u64 = 0xda942042e4dd58b5;
+
+/// Multipliers for a 32-bit LCG (an LCG with an odd `add` value).
+///
+/// These are from the tables of L'Ecuyer, "Tables of linear congruential
+/// generators of different sizes and good lattice structure" (1999). Any of
+/// them will give a full period generator as long as the `add` is odd.
+pub const LCG_MULS_32: [u32; 4] = [PCG_MUL_32, 2891336453, 29943829, 32310901];
+
+/// Multipliers for a 32-bit MCG (an LCG with an `add` of 0).
+///
+/// These are from the same tables as [LCG_MULS_32]. Any of them will give the
+/// maximum possible period for an MCG (`2**30`) as long as the state is odd.
+pub const MCG_MULS_32: [u32; 2] = [1597334677, 741103597];
+
+/// Multipliers for a 64-bit LCG (an LCG with an odd `add` value).
+///
+/// These are from the same tables as [LCG_MULS_32]. Any of them will give a
+/// full period generator as long as the `add` is odd.
+pub const LCG_MULS_64: [u64; 4] =
+  [PCG_MUL_64, 2862933555777941757, 3202034522624059733, 3935559000370003845];
+
+/// Multipliers for a 64-bit MCG (an LCG with an `add` of 0).
+///
+/// These are from the same tables as [LCG_MULS_32]. Any of them will give the
+/// maximum possible period for an MCG (`2**62`) as long as the state is odd.
+pub const MCG_MULS_64: [u64; 2] = [1181783497276652981, 2685821657736338717];
+
 /// Advance a 32-bit LCG's state.
 #[inline]
 #[must_use]
@@ -30,14 +79,14 @@ pub const fn lcg32_jump(mul: u32, add: u32, state: u32, mut delta: u32) -> u32 {
   state.wrapping_mul(acc_mult).wrapping_add(acc_plus)
 }
 
-/// Advance a 32-bit LCG's state.
+/// Advance a 64-bit LCG's state.
 #[inline]
 #[must_use]
 pub const fn lcg64_step(mul: u64, add: u64, state: u64) -> u64 {
   state.wrapping_mul(mul).wrapping_add(add)
 }
 
-/// Advance a 32-bit LCG by `delta` steps in `log2(delta)` time.
+/// Advance a 64-bit LCG by `delta` steps in `log2(delta)` time.
 #[inline]
 #[must_use]
 pub const fn lcg64_jump(mul: u64, add: u64, state: u64, mut delta: u64) -> u64 {
@@ -57,14 +106,14 @@ pub const fn lcg64_jump(mul: u64, add: u64, state: u64, mut delta: u64) -> u64 {
   state.wrapping_mul(acc_mult).wrapping_add(acc_plus)
 }
 
-/// Advance a 32-bit LCG's state.
+/// Advance a 128-bit LCG's state.
 #[inline]
 #[must_use]
 pub const fn lcg128_step(mul: u128, add: u128, state: u128) -> u128 {
   state.wrapping_mul(mul).wrapping_add(add)
 }
 
-/// Advance a 32-bit LCG by `delta` steps in `log2(delta)` time.
+/// Advance a 128-bit LCG by `delta` steps in `log2(delta)` time.
 #[inline]
 #[must_use]
 pub const fn lcg128_jump(mul: u128, add: u128, state: u128, mut delta: u128) -> u128 {
@@ -112,6 +161,132 @@ pub const fn xsl_rr_u128_to_u64(state: u128) -> u64 {
   folded_bits.rotate_right(rot_amount)
 }
 
+/// "Double xor-shift multiply", `u128` down to `u64`.
+///
+/// This is the output function of NumPy's `PCG64DXSM`, and it's meant to be
+/// paired with the [cheap multiplier](PCG_CHEAP_MUL_128).
+#[inline]
+#[must_use]
+pub const fn dxsm_u128_to_u64(state: u128) -> u64 {
+  // Note: The high half is hashed with the cheap multiplier, and then
+  // multiplied by the (forced odd) low half. This means that the final output
+  // depends on all 128 bits of the state.
+  let mut hi: u64 = (state >> 64) as u64;
+  let lo: u64 = (state as u64) | 1;
+  hi ^= hi >> 32;
+  hi = hi.wrapping_mul(PCG_CHEAP_MUL_128);
+  hi ^= hi >> 48;
+  hi.wrapping_mul(lo)
+}
+
+/// Hashes a 1D position into a `u32`.
+///
+/// This is a "Squirrel noise" style hash (from Squirrel Eiserloh's GDC 2017
+/// talk). Each `seed` gives an unrelated set of outputs, and the output for a
+/// given `seed` and position never changes. This lets you get random values
+/// for procedural content without having to generate the content in any
+/// particular order.
+#[inline]
+#[must_use]
+pub const fn noise_1d(seed: u32, x: i32) -> u32 {
+  const BIT_NOISE1: u32 = 0xB5297A4D;
+  const BIT_NOISE2: u32 = 0x68E31DA4;
+  const BIT_NOISE3: u32 = 0x1B56C4E9;
+  let mut mangled = x as u32;
+  mangled = mangled.wrapping_mul(BIT_NOISE1);
+  mangled = mangled.wrapping_add(seed);
+  mangled ^= mangled >> 8;
+  mangled = mangled.wrapping_add(BIT_NOISE2);
+  mangled ^= mangled << 8;
+  mangled = mangled.wrapping_mul(BIT_NOISE3);
+  mangled ^= mangled >> 8;
+  mangled
+}
+
+/// Hashes a 2D position into a `u32`.
+///
+/// See [noise_1d].
+#[inline]
+#[must_use]
+pub const fn noise_2d(seed: u32, x: i32, y: i32) -> u32 {
+  const PRIME_Y: i32 = 198491317;
+  noise_1d(seed, x.wrapping_add(PRIME_Y.wrapping_mul(y)))
+}
+
+/// Hashes a 3D position into a `u32`.
+///
+/// See [noise_1d].
+#[inline]
+#[must_use]
+pub const fn noise_3d(seed: u32, x: i32, y: i32, z: i32) -> u32 {
+  const PRIME_Y: i32 = 198491317;
+  const PRIME_Z: i32 = 6542989;
+  noise_1d(seed, x.wrapping_add(PRIME_Y.wrapping_mul(y)).wrapping_add(PRIME_Z.wrapping_mul(z)))
+}
+
+/// The "golden gamma" used by SplitMix64, `2**64` divided by the golden ratio.
+pub const GOLDEN_GAMMA_64: u64 = 0x9E3779B97F4A7C15;
+
+/// The output mixing function of SplitMix64.
+///
+/// This is a bijection on `u64` with very good avalanche: every input bit
+/// affects every output bit.
+#[inline]
+#[must_use]
+pub const fn mix64(mut z: u64) -> u64 {
+  z = (z ^ (z >> 30)).wrapping_mul(0xBF58476D1CE4E5B9);
+  z = (z ^ (z >> 27)).wrapping_mul(0x94D049BB133111EB);
+  z ^ (z >> 31)
+}
+
+/// Gives the output at `index` of a SplitMix64 generator seeded with `seed`.
+///
+/// SplitMix64 is too weak to use as a main generator, but it's a very good way
+/// to expand a single `u64` into any number of well mixed seed values.
+#[inline]
+#[must_use]
+pub const fn splitmix64(seed: u64, index: u64) -> u64 {
+  mix64(seed.wrapping_add(index.wrapping_add(1).wrapping_mul(GOLDEN_GAMMA_64)))
+}
+
+/// Hashes bytes into a `u64`, such as for seeding a generator.
+///
+/// This is a `const fn`, so a seed phrase can be turned into a seed value at
+/// compile time. Each `key` value gives an unrelated hash function.
+///
+/// This is **not** a cryptographic hash.
+#[inline]
+#[must_use]
+pub const fn hash_bytes64(bytes: &[u8], key: u64) -> u64 {
+  let mut h: u64 = mix64(key ^ (bytes.len() as u64).wrapping_mul(GOLDEN_GAMMA_64));
+  let mut i = 0;
+  while i + 8 <= bytes.len() {
+    let word = u64::from_le_bytes([
+      bytes[i],
+      bytes[i + 1],
+      bytes[i + 2],
+      bytes[i + 3],
+      bytes[i + 4],
+      bytes[i + 5],
+      bytes[i + 6],
+      bytes[i + 7],
+    ]);
+    h = mix64(h ^ word).wrapping_add(GOLDEN_GAMMA_64);
+    i += 8;
+  }
+  // Note: Any trailing bytes are packed into one final word. Because
+  // the total length was mixed in at the start, trailing zero bytes can't be
+  // confused with a shorter input.
+  let mut tail: u64 = 0;
+  let mut shift = 0;
+  while i < bytes.len() {
+    tail |= (bytes[i] as u64) << shift;
+    shift += 8;
+    i += 1;
+  }
+  mix64(h ^ tail)
+}
+
 /// Returns `k` with probability `2^(-k-1)`, a "binary exponential
 /// distribution".
 #[inline]
@@ -148,7 +323,7 @@ pub fn ieee754_random_f32<F: FnMut() -> u32>(mut f: F, signed: bool) -> f32 {
   let r: u32 = f();
 
   debug_assert!(num_rest_bits >= 0);
-  debug_assert!(core::mem::size_of::<u32>() * 8 == bit_width as _);
+  debug_assert!(core::mem::size_of::<u32>() * 8 == bit_width as usize);
 
   let mantissa = r >> (bit_width - num_mantissa_bits);
   let (sign_mask, rand_bit, rest_bits);
@@ -200,7 +375,7 @@ pub fn ieee754_random_f64<F: FnMut() -> u32>(mut f: F, signed: bool) -> f64 {
   let r: u64 = ((f() as u64) << 32) | (f() as u64);
 
   debug_assert!(num_rest_bits >= 0);
-  debug_assert!(core::mem::size_of::<u32>() * 8 == bit_width as _);
+  debug_assert!(core::mem::size_of::<u64>() * 8 == bit_width as usize);
 
   let mantissa = r >> (bit_width - num_mantissa_bits);
   let (sign_mask, rand_bit, rest_bits);
@@ -234,3 +409,49 @@ pub fn ieee754_random_f64<F: FnMut() -> u32>(mut f: F, signed: bool) -> f64 {
   let final_exponent = ((exponent + exponent_bias) as u64) << num_mantissa_bits;
   f64::from_bits(sign_mask | final_exponent | mantissa)
 }
+
+#[test]
+fn test_lcg_multipliers() {
+  // An LCG with a power of 2 modulus has a full period when `add` is odd and
+  // `mul % 4 == 1`. An MCG with a power of 2 modulus has the maximum period
+  // when `mul % 8` is 3 or 5. Since the period is always a power of 2, we can
+  // also check it by jumping half of the period and seeing that we're not back
+  // at the start yet.
+  for mul in LCG_MULS_32 {
+    assert_eq!(mul % 4, 1);
+    assert_ne!(lcg32_jump(mul, PCG_INC_32, 0, 1 << 31), 0);
+  }
+  for mul in MCG_MULS_32 {
+    assert!(mul % 8 == 3 || mul % 8 == 5);
+    assert_eq!(lcg32_jump(mul, 0, 1, 1 << 30), 1);
+    assert_ne!(lcg32_jump(mul, 0, 1, 1 << 29), 1);
+  }
+  for mul in LCG_MULS_64 {
+    assert_eq!(mul % 4, 1);
+    assert_ne!(lcg64_jump(mul, PCG_INC_64, 0, 1 << 63), 0);
+  }
+  for mul in MCG_MULS_64 {
+    assert!(mul % 8 == 3 || mul % 8 == 5);
+    assert_eq!(lcg64_jump(mul, 0, 1, 1 << 62), 1);
+    assert_ne!(lcg64_jump(mul, 0, 1, 1 << 61), 1);
+  }
+  for mul in [PCG_MUL_128, PCG_CHEAP_MUL_128 as u128] {
+    assert_eq!(mul % 4, 1);
+    assert_ne!(lcg128_jump(mul, PCG_INC_128, 0, 1 << 127), 0);
+  }
+  assert_eq!(PCG_INC_32 % 2, 1);
+  assert_eq!(PCG_INC_64 % 2, 1);
+  assert_eq!(PCG_INC_128 % 2, 1);
+}
+
+#[test]
+fn test_ieee754_random_f64() {
+  for words in [[u32::MAX, u32::MAX], [0, 0x10], [0x8000_0000, 0x104], [0x1234_5678, 0x9abc_def0]] {
+    let mut it = words.into_iter();
+    let x = ieee754_random_f64(|| it.next().unwrap(), false);
+    assert!((0.0..=1.0).contains(&x), "{x}");
+    let mut it = words.into_iter();
+    let x = ieee754_random_f64(|| it.next().unwrap(), true);
+    assert!((-1.0..=1.0).contains(&x), "{x}");
+  }
+}
@@ -0,0 +1,120 @@
+//! Known-answer tests against the reference PCG C implementation (pcg-c).
+//!
+//! The vectors here were generated by the pcg-c functions named in each test.
+//! The NumPy `PCG64DXSM`, the `MCG` types, and `Philox4x32` each have their
+//! known-answer tests next to their own code.
+
+use crate::{formulas::*, BoundedRandU16, PCG32, PCG32K};
+
+/// What `pcg32_srandom_r` does.
+const fn pcg32_srandom(initstate: u64, initseq: u64) -> PCG32 {
+  let inc = (initseq << 1) | 1;
+  let state = lcg64_step(PCG_MUL_64, inc, 0).wrapping_add(initstate);
+  PCG32::new(lcg64_step(PCG_MUL_64, inc, state), inc)
+}
+
+#[test]
+fn test_pcg32_srandom_known_answers() {
+  // `pcg32_srandom_r(&rng, 42, 54)` then `pcg32_random_r(&rng)`, as in the
+  // pcg-c demo program.
+  let mut x = pcg32_srandom(42, 54);
+  assert_eq!(x.state, 0x185706b82c2e03f8);
+  assert_eq!(x.inc, 0x6d);
+  for expected in [0xa15c02b7_u32, 0x7b47f409, 0xba1d3330, 0x83d2f293, 0xbfa4784b, 0xcbed606e] {
+    assert_eq!(x.next_u32(), expected);
+  }
+}
+
+#[test]
+fn test_pcg32_seed_known_answers() {
+  // `PCG32::seed` doesn't add the seed in the middle of two steps the way that
+  // `pcg32_srandom_r` does, but it's still equivalent to `pcg32_srandom_r`
+  // with an `initstate` of `((seed << 1) | 1) - ((inc << 1) | 1)`.
+  let (seed, inc) = (0x0123456789abcdef_u64, 0xfedcba9876543210_u64);
+  let mut x = PCG32::seed(seed, inc);
+  let initstate = ((seed << 1) | 1).wrapping_sub((inc << 1) | 1);
+  assert_eq!(x.state, pcg32_srandom(initstate, inc).state);
+  assert_eq!(x.state, 0x87c6cca6b62d6b54);
+  for expected in [0xaae0f8dd_u32, 0x410ae6f9, 0x59d5c62e, 0x246e3aff, 0x90c11f95, 0x270e1733] {
+    assert_eq!(x.next_u32(), expected);
+  }
+}
+
+#[test]
+fn test_pcg32_jump_known_answers() {
+  // `pcg32_advance_r` from the state of `pcg32_srandom_r(&rng, 42, 54)`.
+  let base = pcg32_srandom(42, 54);
+  for (delta, expected) in [
+    (1000, 0x77d57a2a8f8fc510_u64),
+    ((1 << 40) + 7, 0x90bbff9f2fb94e6f),
+    (u64::MAX, 0x0000000000000097),
+  ] {
+    let mut x = base.clone();
+    x.jump(delta);
+    assert_eq!(x.state, expected, "{delta}");
+  }
+  let mut x = base;
+  x.jump(1000);
+  assert_eq!(x.next_u32(), 0xefebeab3);
+}
+
+#[test]
+fn test_lcg_jump_known_answers() {
+  // `pcg_advance_lcg_32` and `pcg_advance_lcg_128` with the default multiplier
+  // and increment.
+  assert_eq!(lcg32_jump(PCG_MUL_32, PCG_INC_32, 1, 12345), 0x27925922);
+  assert_eq!(lcg128_jump(PCG_MUL_128, PCG_INC_128, 42, 1000), 0x9b6fe7c7307a751f_25c6e33ee47132d2);
+}
+
+#[test]
+fn test_xsl_rr_known_answers() {
+  // `pcg64_srandom_r(&rng, 42, 54)` then `pcg64_random_r(&rng)`. The 128-bit
+  // generators of pcg-c step the state before using it for output.
+  let inc = (54 << 1) | 1;
+  let mut state = lcg128_step(PCG_MUL_128, inc, 0).wrapping_add(42);
+  state = lcg128_step(PCG_MUL_128, inc, state);
+  assert_eq!(state, 0xde2bce05be013be3_d3f6c45a41e54320);
+  for expected in [
+    0x86b1da1d72062b68_u64,
+    0x1304aa46c9853d39,
+    0xa3670e9e0dd50358,
+    0xf9090e529a7dae00,
+    0xc85b9fd837996f2c,
+    0x606121f8e3919196,
+  ] {
+    state = lcg128_step(PCG_MUL_128, inc, state);
+    assert_eq!(xsl_rr_u128_to_u64(state), expected);
+  }
+}
+
+#[test]
+fn test_pcg32k_known_answers() {
+  // pcg-c has no extended generators, so these are only this crate's own
+  // outputs (the same as in version 5.1), kept to catch accidental changes.
+  let mut x = PCG32K::<4>::seed(42, [1, 2, 3, 4]);
+  let out: [u32; 6] = core::array::from_fn(|_| x.next_u32());
+  assert_eq!(out, [0xda99cdb6_u32, 0x33a1c232, 0x4b0457a5, 0x483c5d6b, 0xc78bf20c, 0x46763623]);
+}
+
+#[test]
+#[ignore = "exhaustive and slow, run with `cargo test --release -- --ignored`"]
+fn test_bounded_rand_u16_exactly_uniform() {
+  // For every count, every output must be given by exactly the same number of
+  // accepted inputs. Since `place_in_range` is monotonic in its input, each
+  // output's inputs are contiguous and can be counted in one pass.
+  for count in 1..=u16::MAX {
+    let b = BoundedRandU16::new(count);
+    let expected = (1 << 16) / u32::from(count);
+    let (mut current, mut run) = (0, 0);
+    for val in 0..=u16::MAX {
+      if let Some(out) = b.place_in_range(val) {
+        if out != current {
+          assert_eq!((run, out), (expected, current + 1), "count {count}");
+          (current, run) = (out, 0);
+        }
+        run += 1;
+      }
+    }
+    assert_eq!((run, current), (expected, count - 1), "count {count}");
+  }
+}
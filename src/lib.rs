@@ -14,21 +14,130 @@
 //! ## Using This Crate
 //!
 //! * Create a [PCG32] or [PCG32K] value as your generator.
+//!   * If you need to match a NumPy stream, use [PCG64DXSM].
+//!   * If you only need one stream and want a bit more speed, use [MCG32].
+//!   * If you need random access to any point of a stream, use [Philox4x32].
+//!   * If you need values tied to a position in a procedural world, use
+//!     [PositionGen].
 //!   * If you enable this crate's `getrandom` cargo feature then both types
 //!     will have constructor functions to handle seeding a generator from the
 //!     [getrandom](getrandom::getrandom) function.
+//!   * If `getrandom` isn't available, `from_weak_entropy` can still give
+//!     varied (but **not** secure) seeds.
+//!   * If you need many independent generators from one master seed, use a
+//!     [SeedSeq].
 //! * Call `next_u32` on the generator to get pseudo-random `u32` values.
 //! * At your option, import the [Gen32] trait for various extension methods.
+//! * The [testing] module has generators with scripted output, for forcing
+//!   particular results in your own unit tests.
+//! * A [Recorder] logs everything drawn from a generator, and a [Replayer]
+//!   plays that log back. With the `alloc` cargo feature the log can be a
+//!   `Vec`.
+//! * If you enable this crate's `std` cargo feature then there's a lazily
+//!   seeded generator for each thread, and free functions such as `d6()` and
+//!   `shuffle()` that use it, and the `quality` module has statistical tests
+//!   for checking a generator.
+//! * If you enable this crate's `rand_core` cargo feature then [PCG32] and
+//!   [PCG32K] will implement `RngCore` and `SeedableRng`, and any `RngCore` can
+//!   be wrapped in a `RngCoreGen` to use it as a [Gen32].
+//! * If you enable this crate's `serde` cargo feature then the generators and
+//!   bounded range types can be serialized and deserialized.
+//! * [Gen32] can also pick random `char` values, and fill buffers or strings
+//!   with symbols from an [Alphabet], such as for room codes.
+//! * Any type that implements [Random] can be generated with
+//!   [`random`](Gen32::random), and the `derive` cargo feature lets you derive
+//!   `Random` for your own types.
+//! * If you enable this crate's `rayon` cargo feature then `par_trials` can run
+//!   Monte Carlo trials in parallel with reproducible results.
+
+#[cfg(feature = "alloc")]
+extern crate alloc;
+#[cfg(feature = "std")]
+extern crate std;
 
 pub mod formulas;
 use formulas::ieee754_random_f32;
 
+pub mod noise;
+
+pub mod testing;
+
 mod pcg;
 pub use pcg::*;
 
+mod philox;
+pub use philox::*;
+
 mod bounded_rand;
 pub use bounded_rand::*;
 
+mod position_gen;
+pub use position_gen::*;
+
+pub mod state_bytes;
+pub use state_bytes::StateError;
+
+mod error;
+pub use error::*;
+
+mod record;
+pub use record::*;
+
+mod random;
+pub use random::*;
+
+mod alphabet;
+pub use alphabet::*;
+
+/// Derives [Random] for a struct or enum.
+///
+/// A struct generates each of its fields in order. An enum picks one of its
+/// variants uniformly, then generates the fields of that variant in order.
+#[cfg(feature = "derive")]
+#[cfg_attr(docsrs, doc(cfg(feature = "derive")))]
+pub use randomize_derive::Random;
+
+// lets the derive macro's `::randomize` paths work inside this crate's tests.
+#[cfg(test)]
+extern crate self as randomize;
+
+mod seed_seq;
+pub use seed_seq::*;
+
+mod weak_entropy;
+pub use weak_entropy::*;
+
+#[cfg(feature = "rand_core")]
+#[cfg_attr(docsrs, doc(cfg(feature = "rand_core")))]
+mod rand_core_impls;
+#[cfg(feature = "rand_core")]
+#[cfg_attr(docsrs, doc(cfg(feature = "rand_core")))]
+pub use rand_core_impls::*;
+
+#[cfg(feature = "serde")]
+mod serde_impls;
+
+#[cfg(feature = "rayon")]
+#[cfg_attr(docsrs, doc(cfg(feature = "rayon")))]
+mod par;
+#[cfg(feature = "rayon")]
+#[cfg_attr(docsrs, doc(cfg(feature = "rayon")))]
+pub use par::*;
+
+#[cfg(feature = "std")]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+pub mod quality;
+
+#[cfg(feature = "std")]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+mod thread_rng;
+#[cfg(feature = "std")]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+pub use thread_rng::*;
+
+#[cfg(test)]
+mod known_answers;
+
 /// A trait for pseudo-random number generators with 32-bit output per step.
 pub trait Gen32 {
   /// Makes the generator create the next output.
@@ -48,51 +157,148 @@ pub trait Gen32 {
     (self.next_u32() as i32) < 0
   }
 
+  /// Gives a value from a bounded range.
+  ///
+  /// All of the bounded helpers here (such as the dice and
+  /// [shuffle](Self::shuffle)) sample through this method or
+  /// [sample_u16](Self::sample_u16), so a wrapper that overrides both sees
+  /// every bounded result.
+  #[inline]
+  fn sample_u32(&mut self, bounded: BoundedRandU32) -> u32 {
+    bounded.sample(|| self.next_u32())
+  }
+
+  /// Gives a value from a bounded range, using the high half of each `u32`.
+  #[inline]
+  fn sample_u16(&mut self, bounded: BoundedRandU16) -> u16 {
+    bounded.sample(|| (self.next_u32() >> 16) as u16)
+  }
+
+  /// Generates a value of any type that implements [Random].
+  #[inline]
+  fn random<T: Random>(&mut self) -> T
+  where
+    Self: Sized,
+  {
+    T::random(self)
+  }
+
+  /// The same as [random](Self::random).
+  ///
+  /// `gen` is a reserved keyword in the 2024 edition, where this can only be
+  /// called as `r#gen`, so prefer `random`.
+  #[inline]
+  fn gen<T: Random>(&mut self) -> T
+  where
+    Self: Sized,
+  {
+    T::random(self)
+  }
+
   /// Gives a value in the range `0.0 ..= 1.0`
   #[inline]
   fn next_f32_unit(&mut self) -> f32 {
     ieee754_random_f32(|| self.next_u32(), false)
   }
 
+  /// Gives a uniformly distributed Unicode scalar value.
+  ///
+  /// This is any `char` value at all, so it's very likely to be unassigned or
+  /// from a script that your font can't show.
+  #[inline]
+  fn next_char(&mut self) -> char {
+    // Pick from all scalar values with the surrogate range cut out, then move
+    // the values at or above the cut up past it.
+    const SURROGATES: u32 = 0xE000 - 0xD800;
+    const SCALARS: BoundedRandU32 = BoundedRandU32::new(0x11_0000 - SURROGATES);
+    let mut x = self.sample_u32(SCALARS);
+    if x >= 0xD800 {
+      x += SURROGATES;
+    }
+    char::from_u32(x).unwrap()
+  }
+
+  /// Picks a `char` from the slice given, each with equal chance.
+  ///
+  /// ## Panics
+  /// * If the slice is empty or has more than `u32::MAX` elements.
+  #[inline]
+  fn next_char_in(&mut self, charset: &[char]) -> char {
+    let count = u32::try_from(charset.len()).expect("charset is too long");
+    let i = self.sample_u32(BoundedRandU32::try_new(count).expect("charset is empty"));
+    charset[i as usize]
+  }
+
+  /// Fills a buffer with symbols picked from an [Alphabet].
+  #[inline]
+  fn fill_from_alphabet(&mut self, alphabet: &Alphabet<'_>, out: &mut [u8]) {
+    for b in out {
+      *b = alphabet.symbols()[self.sample_u32(alphabet.bounded()) as usize];
+    }
+  }
+
+  /// Makes a `String` of symbols picked from an [Alphabet].
+  #[cfg(feature = "alloc")]
+  #[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+  #[inline]
+  fn string_from_alphabet(&mut self, alphabet: &Alphabet<'_>, len: usize) -> alloc::string::String {
+    let symbols = alphabet.symbols();
+    (0..len).map(|_| char::from(symbols[self.sample_u32(alphabet.bounded()) as usize])).collect()
+  }
+
+  /// Shuffles the elements of a slice into a uniformly random order.
+  ///
+  /// ## Panics
+  /// * If the slice has more than `u32::MAX` elements.
+  #[inline]
+  fn shuffle<T>(&mut self, items: &mut [T]) {
+    // Fisher-Yates: each position swaps with a random position at or below it.
+    for i in (1..items.len()).rev() {
+      let count = u32::try_from(i + 1).expect("slice is too long to shuffle");
+      let j = self.sample_u32(BoundedRandU32::new(count));
+      items.swap(i, j as usize);
+    }
+  }
+
   /// Gives a value in the range `1 ..= 4`
   #[inline]
   fn d4(&mut self) -> i32 {
-    let base = self.next_u32() >> 30;
-    base as i32 + 1
+    let base = self.sample_u16(BoundedRandU16::_4);
+    i32::from(base) + 1
   }
 
   /// Gives a value in the range `1 ..= 6`
   #[inline]
   fn d6(&mut self) -> i32 {
-    let base = BoundedRandU16::_6.sample(|| (self.next_u32() >> 16) as u16);
+    let base = self.sample_u16(BoundedRandU16::_6);
     i32::from(base) + 1
   }
 
   /// Gives a value in the range `1 ..= 8`
   #[inline]
   fn d8(&mut self) -> i32 {
-    let base = self.next_u32() >> 29;
-    base as i32 + 1
+    let base = self.sample_u16(BoundedRandU16::_8);
+    i32::from(base) + 1
   }
 
   /// Gives a value in the range `1 ..= 10`
   #[inline]
   fn d10(&mut self) -> i32 {
-    let base = BoundedRandU16::_10.sample(|| (self.next_u32() >> 16) as u16);
+    let base = self.sample_u16(BoundedRandU16::_10);
     i32::from(base) + 1
   }
 
   /// Gives a value in the range `1 ..= 12`
   #[inline]
   fn d12(&mut self) -> i32 {
-    let base = BoundedRandU16::_12.sample(|| (self.next_u32() >> 16) as u16);
+    let base = self.sample_u16(BoundedRandU16::_12);
     i32::from(base) + 1
   }
 
   /// Gives a value in the range `1 ..= 20`
   #[inline]
   fn d20(&mut self) -> i32 {
-    let base = BoundedRandU16::_20.sample(|| (self.next_u32() >> 16) as u16);
+    let base = self.sample_u16(BoundedRandU16::_20);
     i32::from(base) + 1
   }
 }
@@ -103,6 +309,42 @@ impl Gen32 for PCG32 {
     PCG32::next_u32(self)
   }
 }
+impl Gen32 for Leapfrog {
+  #[inline]
+  fn next_u32(&mut self) -> u32 {
+    Leapfrog::next_u32(self)
+  }
+}
+impl Gen32 for MCG32 {
+  #[inline]
+  fn next_u32(&mut self) -> u32 {
+    MCG32::next_u32(self)
+  }
+}
+impl Gen32 for MCG64 {
+  #[inline]
+  fn next_u32(&mut self) -> u32 {
+    MCG64::next_u32(self)
+  }
+}
+impl Gen32 for Philox4x32 {
+  #[inline]
+  fn next_u32(&mut self) -> u32 {
+    Philox4x32::next_u32(self)
+  }
+}
+impl Gen32 for PositionGen {
+  #[inline]
+  fn next_u32(&mut self) -> u32 {
+    PositionGen::next_u32(self)
+  }
+}
+impl Gen32 for PCG64DXSM {
+  #[inline]
+  fn next_u32(&mut self) -> u32 {
+    PCG64DXSM::next_u32(self)
+  }
+}
 impl<const K: usize> Gen32 for PCG32K<K> {
   #[inline]
   fn next_u32(&mut self) -> u32 {
@@ -0,0 +1,639 @@
+//! Seeded gradient noise and value noise, with fractal layering.
+//!
+//! All noise types here are built from a [PermutationTable], which is shuffled
+//! by a [PCG32] seeded with the value you provide. This means that the same
+//! seed gives the same noise on every platform, and you can use one seed for
+//! both the dice rolls and the terrain of a game.
+//!
+//! Outputs are roughly within `-1.0 ..= 1.0`.
+
+use crate::{BoundedRandU32, PCG32};
+
+/// A source of coherent noise.
+pub trait Noise {
+  /// Gets the noise value at a 2D position.
+  fn get_2d(&self, x: f32, y: f32) -> f32;
+
+  /// Gets the noise value at a 3D position.
+  fn get_3d(&self, x: f32, y: f32, z: f32) -> f32;
+}
+
+/// A shuffled table of all `u8` values.
+///
+/// The table is stored twice in a row, so that indexing with the sum of two
+/// `u8` values never goes out of bounds.
+#[derive(Debug, Clone)]
+pub struct PermutationTable {
+  perm: [u8; 512],
+}
+impl PermutationTable {
+  /// Makes a new table, shuffled by a [PCG32] with the given seed.
+  #[inline]
+  #[must_use]
+  pub fn new(seed: u64) -> Self {
+    let mut base: [u8; 256] = core::array::from_fn(|i| i as u8);
+    let mut gen = PCG32::seed(seed, 0);
+    // Fisher-Yates: each position swaps with a random position at or below it.
+    for i in (1..base.len()).rev() {
+      let j = BoundedRandU32::new(i as u32 + 1).sample(|| gen.next_u32());
+      base.swap(i, j as usize);
+    }
+    let perm: [u8; 512] = core::array::from_fn(|i| base[i & 255]);
+    Self { perm }
+  }
+
+  #[inline]
+  #[must_use]
+  const fn hash_2d(&self, x: i32, y: i32) -> u8 {
+    let y = self.perm[(y & 255) as usize] as usize;
+    self.perm[(x & 255) as usize + y]
+  }
+
+  #[inline]
+  #[must_use]
+  const fn hash_3d(&self, x: i32, y: i32, z: i32) -> u8 {
+    let z = self.perm[(z & 255) as usize] as usize;
+    let y = self.perm[(y & 255) as usize + z] as usize;
+    self.perm[(x & 255) as usize + y]
+  }
+
+  #[inline]
+  #[must_use]
+  const fn hash_4d(&self, x: i32, y: i32, z: i32, w: i32) -> u8 {
+    let w = self.perm[(w & 255) as usize] as usize;
+    let z = self.perm[(z & 255) as usize + w] as usize;
+    let y = self.perm[(y & 255) as usize + z] as usize;
+    self.perm[(x & 255) as usize + y]
+  }
+}
+
+/// Ken Perlin's "improved" gradient noise.
+///
+/// The value at every integer lattice point is 0.
+#[derive(Debug, Clone)]
+pub struct Perlin {
+  table: PermutationTable,
+}
+impl Perlin {
+  /// Makes a new noise source using the given seed.
+  #[inline]
+  #[must_use]
+  pub fn new(seed: u64) -> Self {
+    Self { table: PermutationTable::new(seed) }
+  }
+}
+impl Noise for Perlin {
+  #[inline]
+  fn get_2d(&self, x: f32, y: f32) -> f32 {
+    let ((xi, xf), (yi, yf)) = (split(x as f64), split(y as f64));
+    let (xn, yn) = (xi.wrapping_add(1), yi.wrapping_add(1));
+    let (u, v) = (fade(xf), fade(yf));
+    let t = &self.table;
+    let n00 = grad_2d(t.hash_2d(xi, yi), xf, yf);
+    let n10 = grad_2d(t.hash_2d(xn, yi), xf - 1.0, yf);
+    let n01 = grad_2d(t.hash_2d(xi, yn), xf, yf - 1.0);
+    let n11 = grad_2d(t.hash_2d(xn, yn), xf - 1.0, yf - 1.0);
+    lerp(v, lerp(u, n00, n10), lerp(u, n01, n11))
+  }
+
+  #[inline]
+  fn get_3d(&self, x: f32, y: f32, z: f32) -> f32 {
+    let ((xi, xf), (yi, yf), (zi, zf)) = (split(x as f64), split(y as f64), split(z as f64));
+    let (xn, yn, zn) = (xi.wrapping_add(1), yi.wrapping_add(1), zi.wrapping_add(1));
+    let (u, v, w) = (fade(xf), fade(yf), fade(zf));
+    let t = &self.table;
+    let n000 = grad_3d(t.hash_3d(xi, yi, zi), xf, yf, zf);
+    let n100 = grad_3d(t.hash_3d(xn, yi, zi), xf - 1.0, yf, zf);
+    let n010 = grad_3d(t.hash_3d(xi, yn, zi), xf, yf - 1.0, zf);
+    let n110 = grad_3d(t.hash_3d(xn, yn, zi), xf - 1.0, yf - 1.0, zf);
+    let n001 = grad_3d(t.hash_3d(xi, yi, zn), xf, yf, zf - 1.0);
+    let n101 = grad_3d(t.hash_3d(xn, yi, zn), xf - 1.0, yf, zf - 1.0);
+    let n011 = grad_3d(t.hash_3d(xi, yn, zn), xf, yf - 1.0, zf - 1.0);
+    let n111 = grad_3d(t.hash_3d(xn, yn, zn), xf - 1.0, yf - 1.0, zf - 1.0);
+    let x00 = lerp(u, n000, n100);
+    let x10 = lerp(u, n010, n110);
+    let x01 = lerp(u, n001, n101);
+    let x11 = lerp(u, n011, n111);
+    lerp(w, lerp(v, x00, x10), lerp(v, x01, x11))
+  }
+}
+
+/// OpenSimplex2 noise.
+///
+/// This is similar to [Perlin] noise, but it's computed over a triangular (2D)
+/// or body-centered cubic (3D) lattice instead of a square or cube lattice.
+/// This gives far fewer directional artifacts.
+///
+/// This follows the "fast" variant of Kurt Spencer's OpenSimplex2, but the
+/// gradients are picked with the [PermutationTable] like the other noise types
+/// here, so the exact values won't match other OpenSimplex2 implementations.
+#[derive(Debug, Clone)]
+pub struct OpenSimplex2 {
+  table: PermutationTable,
+}
+impl OpenSimplex2 {
+  /// Makes a new noise source using the given seed.
+  #[inline]
+  #[must_use]
+  pub fn new(seed: u64) -> Self {
+    Self { table: PermutationTable::new(seed) }
+  }
+}
+impl Noise for OpenSimplex2 {
+  #[inline]
+  fn get_2d(&self, x: f32, y: f32) -> f32 {
+    // (sqrt(3) - 1) / 2
+    const SKEW: f64 = 0.36602540378443865;
+    // (1 / sqrt(3) - 1) / 2
+    const UNSKEW: f32 = -0.21132487;
+    const NORMALIZER: f32 = 0.010016341;
+    // Skew the input onto a square lattice to find which cell we're in, then
+    // unskew our offset within that cell back to the triangular lattice. The
+    // skew is done with `f64` so that it can't overflow.
+    let s = (x as f64 + y as f64) * SKEW;
+    let ((xb, xi), (yb, yi)) = (split(x as f64 + s), split(y as f64 + s));
+    let (xn, yn) = (xb.wrapping_add(1), yb.wrapping_add(1));
+    let t = (xi + yi) * UNSKEW;
+    let (dx0, dy0) = (xi + t, yi + t);
+    let tab = &self.table;
+    // The cell's first and last corners always matter.
+    let (dx1, dy1) = (dx0 - (1.0 + 2.0 * UNSKEW), dy0 - (1.0 + 2.0 * UNSKEW));
+    let mut value = opensimplex_corner_2d(tab.hash_2d(xb, yb), dx0, dy0)
+      + opensimplex_corner_2d(tab.hash_2d(xn, yn), dx1, dy1);
+    // Of the other two corners, only the one on our side of the cell's
+    // diagonal is close enough to matter.
+    value += if dy0 > dx0 {
+      opensimplex_corner_2d(tab.hash_2d(xb, yn), dx0 - UNSKEW, dy0 - (UNSKEW + 1.0))
+    } else {
+      opensimplex_corner_2d(tab.hash_2d(xn, yb), dx0 - (UNSKEW + 1.0), dy0 - UNSKEW)
+    };
+    value / NORMALIZER
+  }
+
+  #[inline]
+  fn get_3d(&self, x: f32, y: f32, z: f32) -> f32 {
+    const NORMALIZER: f32 = 0.07969838;
+    // Reflect the input so that the main diagonal of the lattice lines up
+    // with the main diagonal of the input space. Like in 2D, this is done with
+    // `f64` so that it can't overflow.
+    let (x, y, z) = (x as f64, y as f64, z as f64);
+    let r = (2.0 / 3.0) * (x + y + z);
+    // The lattice is two offset copies of a cube lattice. In each copy we use
+    // the closest point and whichever neighbor of it is next closest.
+    let ((mut xb, mut xi), (mut yb, mut yi), (mut zb, mut zi)) =
+      (split_round(r - x), split_round(r - y), split_round(r - z));
+    // -1 if the offset is positive, 1 if it's negative.
+    let mut xs: i32 = if xi >= 0.0 { -1 } else { 1 };
+    let mut ys: i32 = if yi >= 0.0 { -1 } else { 1 };
+    let mut zs: i32 = if zi >= 0.0 { -1 } else { 1 };
+    let (mut ax, mut ay, mut az) = (-(xs as f32) * xi, -(ys as f32) * yi, -(zs as f32) * zi);
+    let mut a = 0.6 - xi * xi - (yi * yi + zi * zi);
+    let tab = &self.table;
+    let mut value = 0.0;
+    for lattice in 0..2 {
+      value += opensimplex_corner_3d(a, tab.hash_4d(xb, yb, zb, lattice), xi, yi, zi);
+      value += if ax >= ay && ax >= az {
+        let hash = tab.hash_4d(xb.wrapping_sub(xs), yb, zb, lattice);
+        opensimplex_corner_3d(a + ax + ax - 1.0, hash, xi + xs as f32, yi, zi)
+      } else if ay > ax && ay >= az {
+        let hash = tab.hash_4d(xb, yb.wrapping_sub(ys), zb, lattice);
+        opensimplex_corner_3d(a + ay + ay - 1.0, hash, xi, yi + ys as f32, zi)
+      } else {
+        let hash = tab.hash_4d(xb, yb, zb.wrapping_sub(zs), lattice);
+        opensimplex_corner_3d(a + az + az - 1.0, hash, xi, yi, zi + zs as f32)
+      };
+      // Move over to the other copy of the lattice, which is offset by half
+      // a cell on every axis.
+      (ax, ay, az) = (0.5 - ax, 0.5 - ay, 0.5 - az);
+      (xi, yi, zi) = (xs as f32 * ax, ys as f32 * ay, zs as f32 * az);
+      a += (0.75 - ax) - (ay + az);
+      xb = xb.wrapping_add((xs < 0) as i32);
+      yb = yb.wrapping_add((ys < 0) as i32);
+      zb = zb.wrapping_add((zs < 0) as i32);
+      (xs, ys, zs) = (-xs, -ys, -zs);
+    }
+    value / NORMALIZER
+  }
+}
+
+/// Value noise.
+///
+/// Each integer lattice point is given a random value, and the points between
+/// are smoothly interpolated. This is cheaper than gradient noise, but it looks
+/// more "blocky".
+#[derive(Debug, Clone)]
+pub struct ValueNoise {
+  table: PermutationTable,
+}
+impl ValueNoise {
+  /// Makes a new noise source using the given seed.
+  #[inline]
+  #[must_use]
+  pub fn new(seed: u64) -> Self {
+    Self { table: PermutationTable::new(seed) }
+  }
+}
+impl Noise for ValueNoise {
+  #[inline]
+  fn get_2d(&self, x: f32, y: f32) -> f32 {
+    let ((xi, xf), (yi, yf)) = (split(x as f64), split(y as f64));
+    let (xn, yn) = (xi.wrapping_add(1), yi.wrapping_add(1));
+    let (u, v) = (fade(xf), fade(yf));
+    let t = &self.table;
+    let n00 = lattice_value(t.hash_2d(xi, yi));
+    let n10 = lattice_value(t.hash_2d(xn, yi));
+    let n01 = lattice_value(t.hash_2d(xi, yn));
+    let n11 = lattice_value(t.hash_2d(xn, yn));
+    lerp(v, lerp(u, n00, n10), lerp(u, n01, n11))
+  }
+
+  #[inline]
+  fn get_3d(&self, x: f32, y: f32, z: f32) -> f32 {
+    let ((xi, xf), (yi, yf), (zi, zf)) = (split(x as f64), split(y as f64), split(z as f64));
+    let (xn, yn, zn) = (xi.wrapping_add(1), yi.wrapping_add(1), zi.wrapping_add(1));
+    let (u, v, w) = (fade(xf), fade(yf), fade(zf));
+    let t = &self.table;
+    let n000 = lattice_value(t.hash_3d(xi, yi, zi));
+    let n100 = lattice_value(t.hash_3d(xn, yi, zi));
+    let n010 = lattice_value(t.hash_3d(xi, yn, zi));
+    let n110 = lattice_value(t.hash_3d(xn, yn, zi));
+    let n001 = lattice_value(t.hash_3d(xi, yi, zn));
+    let n101 = lattice_value(t.hash_3d(xn, yi, zn));
+    let n011 = lattice_value(t.hash_3d(xi, yn, zn));
+    let n111 = lattice_value(t.hash_3d(xn, yn, zn));
+    let x00 = lerp(u, n000, n100);
+    let x10 = lerp(u, n010, n110);
+    let x01 = lerp(u, n001, n101);
+    let x11 = lerp(u, n011, n111);
+    lerp(w, lerp(v, x00, x10), lerp(v, x01, x11))
+  }
+}
+
+/// Fractal Brownian motion: several octaves of a noise source added together.
+///
+/// Each octave has `lacunarity` times the frequency and `gain` times the
+/// amplitude of the octave before it. The total is scaled back down so that
+/// the output stays in the same range as the base noise.
+#[derive(Debug, Clone)]
+pub struct Fbm<N> {
+  /// The base noise.
+  pub noise: N,
+  /// The number of octaves to add together.
+  pub octaves: u32,
+  /// Frequency multiplier between octaves.
+  pub lacunarity: f32,
+  /// Amplitude multiplier between octaves.
+  pub gain: f32,
+}
+impl<N> Fbm<N> {
+  /// Wraps a noise source, using common default settings.
+  ///
+  /// * 4 octaves
+  /// * lacunarity of 2.0
+  /// * gain of 0.5
+  #[inline]
+  #[must_use]
+  pub const fn new(noise: N) -> Self {
+    Self { noise, octaves: 4, lacunarity: 2.0, gain: 0.5 }
+  }
+}
+impl<N: Noise> Noise for Fbm<N> {
+  #[inline]
+  fn get_2d(&self, x: f32, y: f32) -> f32 {
+    layer(self.octaves, self.lacunarity, self.gain, |f| self.noise.get_2d(x * f, y * f))
+  }
+
+  #[inline]
+  fn get_3d(&self, x: f32, y: f32, z: f32) -> f32 {
+    layer(self.octaves, self.lacunarity, self.gain, |f| self.noise.get_3d(x * f, y * f, z * f))
+  }
+}
+
+/// Ridged multi-fractal noise.
+///
+/// This is like [Fbm], but each octave is folded with `1.0 - abs(n)` and then
+/// squared. This gives sharp ridges where the base noise crosses zero, which
+/// is good for mountain ranges.
+#[derive(Debug, Clone)]
+pub struct Ridged<N> {
+  /// The base noise.
+  pub noise: N,
+  /// The number of octaves to add together.
+  pub octaves: u32,
+  /// Frequency multiplier between octaves.
+  pub lacunarity: f32,
+  /// Amplitude multiplier between octaves.
+  pub gain: f32,
+}
+impl<N> Ridged<N> {
+  /// Wraps a noise source, using common default settings.
+  ///
+  /// * 4 octaves
+  /// * lacunarity of 2.0
+  /// * gain of 0.5
+  #[inline]
+  #[must_use]
+  pub const fn new(noise: N) -> Self {
+    Self { noise, octaves: 4, lacunarity: 2.0, gain: 0.5 }
+  }
+}
+impl<N: Noise> Noise for Ridged<N> {
+  #[inline]
+  fn get_2d(&self, x: f32, y: f32) -> f32 {
+    layer(self.octaves, self.lacunarity, self.gain, |f| ridge(self.noise.get_2d(x * f, y * f)))
+  }
+
+  #[inline]
+  fn get_3d(&self, x: f32, y: f32, z: f32) -> f32 {
+    layer(self.octaves, self.lacunarity, self.gain, |f| {
+      ridge(self.noise.get_3d(x * f, y * f, z * f))
+    })
+  }
+}
+
+/// Adds up `octaves` calls to `f`, passing the frequency for each octave.
+#[inline]
+fn layer<F: FnMut(f32) -> f32>(octaves: u32, lacunarity: f32, gain: f32, mut f: F) -> f32 {
+  let mut total = 0.0;
+  let mut frequency = 1.0;
+  let mut amplitude = 1.0;
+  let mut max_amplitude = 0.0;
+  for _ in 0..octaves {
+    total += f(frequency) * amplitude;
+    max_amplitude += amplitude;
+    frequency *= lacunarity;
+    amplitude *= gain;
+  }
+  if max_amplitude > 0.0 {
+    total / max_amplitude
+  } else {
+    0.0
+  }
+}
+
+/// Folds a noise value into a ridge, keeping it within `-1.0 ..= 1.0`.
+#[inline]
+#[must_use]
+fn ridge(n: f32) -> f32 {
+  let r = 1.0 - n.abs();
+  (r * r) * 2.0 - 1.0
+}
+
+/// Splits a value into the lattice cell it's in and its offset within that
+/// cell, without needing `std`.
+///
+/// The offset is in `0.0 ..= 1.0`. The cell wraps around the `i32` range
+/// instead of saturating, so all lattice math that builds on this must wrap
+/// too. Since the noise tables repeat every 256 cells, wrapping doesn't change
+/// the result.
+#[inline]
+#[must_use]
+const fn split(x: f64) -> (i32, f32) {
+  // Past 2**52 every `f64` is already a whole number.
+  const WHOLE: f64 = (1_u64 << 52) as f64;
+  let floor = if -WHOLE < x && x < WHOLE {
+    let i = x as i64 as f64;
+    if i > x {
+      i - 1.0
+    } else {
+      i
+    }
+  } else {
+    x
+  };
+  let cell = (floor % 4294967296.0) as i64 as i32;
+  (cell, (x - floor) as f32)
+}
+
+/// Like [split], but with the nearest lattice point, so the offset is in
+/// `-0.5 ..= 0.5`.
+#[inline]
+#[must_use]
+const fn split_round(x: f64) -> (i32, f32) {
+  let (cell, offset) = split(x + 0.5);
+  (cell, offset - 0.5)
+}
+
+/// Perlin's quintic easing curve.
+#[inline]
+#[must_use]
+const fn fade(t: f32) -> f32 {
+  t * t * t * (t * (t * 6.0 - 15.0) + 10.0)
+}
+
+#[inline]
+#[must_use]
+const fn lerp(t: f32, a: f32, b: f32) -> f32 {
+  a + t * (b - a)
+}
+
+/// Maps a table entry to a lattice value in `-1.0 ..= 1.0`.
+#[inline]
+#[must_use]
+const fn lattice_value(hash: u8) -> f32 {
+  (hash as f32 / 127.5) - 1.0
+}
+
+/// Dot product of `(x, y)` with one of 8 gradient directions.
+#[inline]
+#[must_use]
+const fn grad_2d(hash: u8, x: f32, y: f32) -> f32 {
+  match hash & 7 {
+    0 => x + y,
+    1 => -x + y,
+    2 => x - y,
+    3 => -x - y,
+    4 => x,
+    5 => -x,
+    6 => y,
+    _ => -y,
+  }
+}
+
+/// Dot product of `(x, y, z)` with one of the 12 cube edge gradients.
+#[inline]
+#[must_use]
+const fn grad_3d(hash: u8, x: f32, y: f32, z: f32) -> f32 {
+  // Note: This is the bit trick from Perlin's reference code. The
+  // 16 possible cases cover the 12 cube edges, with 4 of them repeated.
+  let h = hash & 15;
+  let u = if h < 8 { x } else { y };
+  let v = if h < 4 {
+    y
+  } else if h == 12 || h == 14 {
+    x
+  } else {
+    z
+  };
+  (if (h & 1) == 0 { u } else { -u }) + (if (h & 2) == 0 { v } else { -v })
+}
+
+/// The 24 directions used by 2D [OpenSimplex2], evenly spaced around the
+/// circle.
+const OPENSIMPLEX_GRAD_2D: [[f32; 2]; 24] = [
+  [0.9914449, 0.13052619],
+  [0.9238795, 0.38268343],
+  [0.7933533, 0.6087614],
+  [0.6087614, 0.7933533],
+  [0.38268343, 0.9238795],
+  [0.13052619, 0.9914449],
+  [-0.13052619, 0.9914449],
+  [-0.38268343, 0.9238795],
+  [-0.6087614, 0.7933533],
+  [-0.7933533, 0.6087614],
+  [-0.9238795, 0.38268343],
+  [-0.9914449, 0.13052619],
+  [-0.9914449, -0.13052619],
+  [-0.9238795, -0.38268343],
+  [-0.7933533, -0.6087614],
+  [-0.6087614, -0.7933533],
+  [-0.38268343, -0.9238795],
+  [-0.13052619, -0.9914449],
+  [0.13052619, -0.9914449],
+  [0.38268343, -0.9238795],
+  [0.6087614, -0.7933533],
+  [0.7933533, -0.6087614],
+  [0.9238795, -0.38268343],
+  [0.9914449, -0.13052619],
+];
+
+/// The 48 directions used by 3D [OpenSimplex2].
+///
+/// These are all the same length, and they're the gradient set of the
+/// reference OpenSimplex2 code.
+const OPENSIMPLEX_GRAD_3D: [[f32; 3]; 48] = {
+  const A: f32 = 2.2247448;
+  const B: f32 = 3.0862665;
+  const C: f32 = 1.1721513;
+  [
+    [1.0, A, A],
+    [-1.0, A, A],
+    [1.0, -A, A],
+    [-1.0, -A, A],
+    [1.0, A, -A],
+    [-1.0, A, -A],
+    [1.0, -A, -A],
+    [-1.0, -A, -A],
+    [A, 1.0, A],
+    [-A, 1.0, A],
+    [A, -1.0, A],
+    [-A, -1.0, A],
+    [A, 1.0, -A],
+    [-A, 1.0, -A],
+    [A, -1.0, -A],
+    [-A, -1.0, -A],
+    [A, A, 1.0],
+    [-A, A, 1.0],
+    [A, -A, 1.0],
+    [-A, -A, 1.0],
+    [A, A, -1.0],
+    [-A, A, -1.0],
+    [A, -A, -1.0],
+    [-A, -A, -1.0],
+    [B, C, 0.0],
+    [-B, C, 0.0],
+    [B, -C, 0.0],
+    [-B, -C, 0.0],
+    [B, 0.0, C],
+    [-B, 0.0, C],
+    [B, 0.0, -C],
+    [-B, 0.0, -C],
+    [C, B, 0.0],
+    [C, -B, 0.0],
+    [-C, B, 0.0],
+    [-C, -B, 0.0],
+    [0.0, B, C],
+    [0.0, -B, C],
+    [0.0, B, -C],
+    [0.0, -B, -C],
+    [C, 0.0, B],
+    [C, 0.0, -B],
+    [-C, 0.0, B],
+    [-C, 0.0, -B],
+    [0.0, C, B],
+    [0.0, C, -B],
+    [0.0, -C, B],
+    [0.0, -C, -B],
+  ]
+};
+
+/// The contribution of one 2D [OpenSimplex2] lattice point at offset
+/// `(x, y)`.
+#[inline]
+#[must_use]
+const fn opensimplex_corner_2d(hash: u8, x: f32, y: f32) -> f32 {
+  let a = 0.5 - x * x - y * y;
+  if a <= 0.0 {
+    0.0
+  } else {
+    let [gx, gy] = OPENSIMPLEX_GRAD_2D[(hash % 24) as usize];
+    (a * a) * (a * a) * (gx * x + gy * y)
+  }
+}
+
+/// The contribution of one 3D [OpenSimplex2] lattice point at offset
+/// `(x, y, z)`, given its falloff `a`.
+#[inline]
+#[must_use]
+const fn opensimplex_corner_3d(a: f32, hash: u8, x: f32, y: f32, z: f32) -> f32 {
+  if a <= 0.0 {
+    0.0
+  } else {
+    let [gx, gy, gz] = OPENSIMPLEX_GRAD_3D[(hash % 48) as usize];
+    (a * a) * (a * a) * (gx * x + gy * y + gz * z)
+  }
+}
+
+#[test]
+fn test_noise_is_seeded() {
+  let a = Perlin::new(1);
+  let b = Perlin::new(1);
+  let c = Perlin::new(2);
+  assert_eq!(a.get_2d(0.5, 0.25), b.get_2d(0.5, 0.25));
+  assert_ne!(a.get_2d(0.5, 0.25), c.get_2d(0.5, 0.25));
+  // Perlin noise is 0 on the integer lattice.
+  assert_eq!(a.get_2d(3.0, -7.0), 0.0);
+  assert_eq!(a.get_3d(3.0, -7.0, 2.0), 0.0);
+}
+
+#[test]
+fn test_noise_range() {
+  let perlin = Perlin::new(5);
+  let simplex = OpenSimplex2::new(5);
+  let value = ValueNoise::new(5);
+  let fbm = Fbm::new(OpenSimplex2::new(5));
+  let ridged = Ridged::new(Perlin::new(5));
+  let sources: [&dyn Noise; 5] = [&perlin, &simplex, &value, &fbm, &ridged];
+  for source in sources {
+    for i in -50..50 {
+      for j in -50..50 {
+        let (x, y) = (i as f32 * 0.37, j as f32 * 0.29);
+        let n2 = source.get_2d(x, y);
+        let n3 = source.get_3d(x, y, (x - y) * 0.5);
+        assert!((-1.1..=1.1).contains(&n2), "{n2}");
+        assert!((-1.1..=1.1).contains(&n3), "{n3}");
+      }
+    }
+  }
+}
+
+#[test]
+fn test_noise_far_from_origin() {
+  // Positions past the `i32` range must not overflow the lattice math.
+  let perlin = Perlin::new(5);
+  let simplex = OpenSimplex2::new(5);
+  let value = ValueNoise::new(5);
+  let sources: [&dyn Noise; 3] = [&perlin, &simplex, &value];
+  for source in sources {
+    for v in [3e9, -3e9, f32::MAX, f32::MIN] {
+      for n in [
+        source.get_2d(v, 0.5),
+        source.get_2d(0.5, v),
+        source.get_3d(v, v, 0.5),
+        source.get_3d(-v, 0.5, v),
+      ] {
+        assert!(n.is_finite() && (-1.0..=1.0).contains(&n), "{n} at {v}");
+      }
+    }
+  }
+}
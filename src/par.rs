@@ -0,0 +1,74 @@
+use crate::{
+  formulas::{lcg64_jump, lcg64_step, PCG_MUL_64},
+  PCG32,
+};
+use core::iter::Sum;
+use rayon::prelude::*;
+
+/// Runs `n` trials in parallel and sums up the results.
+///
+/// Trial `i` is given the `i`th of `n` [substreams](PCG32::substream) of a
+/// [PCG32] seeded with `PCG32::seed(seed, 0)`, along with the index `i`. Since
+/// each trial's generator only depends on `seed`, `n`, and `i`, every trial
+/// gets the same random values no matter how many threads are used or which
+/// thread runs it. As long as summing `T` values is associative (such as with
+/// integers), the total is exactly the same as a sequential run.
+///
+/// Summing floats is *not* associative, so with floats the total can differ in
+/// the last few bits depending on how the work was split.
+///
+/// ```
+/// use randomize::{par_trials, Gen32, PCG32};
+/// let n = 10_000;
+/// let hits: u64 = par_trials(42, n, |g, _i| (g.d6() >= 4) as u64);
+///
+/// let base = PCG32::seed(42, 0);
+/// let sequential: u64 = (0..n).map(|i| (base.substream(i, n).d6() >= 4) as u64).sum();
+/// assert_eq!(hits, sequential);
+/// ```
+///
+/// ## Panics
+/// * If `n` is 0.
+#[inline]
+pub fn par_trials<T, F>(seed: u64, n: u64, f: F) -> T
+where
+  T: Send + Sum<T>,
+  F: Fn(&mut PCG32, u64) -> T + Sync + Send,
+{
+  assert!(n > 0, "can't run 0 trials");
+  let base = PCG32::seed(seed, 0);
+  // Note: Moving from one substream to the next is a jump by a fixed
+  // amount, which is the same as a single step of another LCG. Each worker
+  // keeps the last trial's position, so a run of trials in a row only pays for
+  // a full `substream` jump once.
+  let block_len = ((1_u128 << 64) / (n as u128)) as u64;
+  let add = lcg64_jump(PCG_MUL_64, base.inc, 0, block_len);
+  let mul = lcg64_jump(PCG_MUL_64, base.inc, 1, block_len).wrapping_sub(add);
+  (0..n)
+    .into_par_iter()
+    .map_init(
+      || None::<(u64, u64)>,
+      |last, i| {
+        let state = match *last {
+          Some((last_i, last_state)) if last_i + 1 == i => lcg64_step(mul, add, last_state),
+          _ => base.substream(i, n).state,
+        };
+        *last = Some((i, state));
+        let mut g = PCG32::new(state, base.inc);
+        f(&mut g, i)
+      },
+    )
+    .sum()
+}
+
+#[test]
+fn test_par_trials_matches_sequential() {
+  let n = 1000;
+  let base = PCG32::seed(7, 0);
+  let sequential: u64 = (0..n).map(|i| base.substream(i, n).next_u32() as u64).sum();
+  for threads in [1, 2, 5] {
+    let pool = rayon::ThreadPoolBuilder::new().num_threads(threads).build().unwrap();
+    let total: u64 = pool.install(|| par_trials(7, n, |g, _| g.next_u32() as u64));
+    assert_eq!(total, sequential);
+  }
+}
@@ -1,4 +1,7 @@
-use crate::formulas::{lcg32_step, lcg64_jump, lcg64_step, xsh_rr_u64_to_u32, PCG_MUL_64};
+use crate::formulas::{
+  dxsm_u128_to_u64, hash_bytes64, lcg128_jump, lcg128_step, lcg32_step, lcg64_jump, lcg64_step,
+  splitmix64, xsh_rr_u64_to_u32, xsl_rr_u128_to_u64, PCG_CHEAP_MUL_128, PCG_MUL_128, PCG_MUL_64,
+};
 
 /// A [Permuted Congruential Generator][wp] with 32-bit output.
 ///
@@ -6,6 +9,7 @@ use crate::formulas::{lcg32_step, lcg64_jump, lcg64_step, xsh_rr_u64_to_u32, PCG
 ///
 /// * Period: `2**64` when `inc` is odd, otherwise less
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct PCG32 {
   /// The generator's state.
   ///
@@ -42,6 +46,36 @@ impl PCG32 {
     Self { state, inc }
   }
 
+  /// Seed a new generator by hashing arbitrary bytes.
+  ///
+  /// Every bit of the input affects both the `state` and the `inc` of the new
+  /// generator. This is a `const fn`, so it can be used to make a generator
+  /// constant.
+  #[inline]
+  #[must_use]
+  pub const fn seed_from_bytes(bytes: &[u8]) -> Self {
+    let h = hash_bytes64(bytes, 0);
+    Self::new(splitmix64(h, 0), splitmix64(h, 1) | 1)
+  }
+
+  /// Seed a new generator from a string, such as a seed phrase typed in by a
+  /// user.
+  ///
+  /// This is the same as [seed_from_bytes](Self::seed_from_bytes) with the
+  /// UTF-8 bytes of the string.
+  ///
+  /// ```
+  /// use randomize::PCG32;
+  /// const WORLD_GEN: PCG32 = PCG32::from_str_seed("world-42");
+  /// let mut g = WORLD_GEN.clone();
+  /// assert_eq!(g.next_u32(), PCG32::from_str_seed("world-42").next_u32());
+  /// ```
+  #[inline]
+  #[must_use]
+  pub const fn from_str_seed(s: &str) -> Self {
+    Self::seed_from_bytes(s.as_bytes())
+  }
+
   /// Create a new generator seeded with data from
   /// [getrandom](getrandom::getrandom).
   ///
@@ -49,7 +83,7 @@ impl PCG32 {
   #[cfg(feature = "getrandom")]
   #[cfg_attr(docsrs, doc(cfg(feature = "getrandom")))]
   #[inline]
-  pub fn from_getrandom() -> Result<Self, getrandom::Error> {
+  pub fn from_getrandom() -> Result<Self, crate::Error> {
     use bytemuck::bytes_of_mut;
 
     let mut buf = [0_u64; 2];
@@ -60,7 +94,7 @@ impl PCG32 {
 
   /// Generate the next `u32` in the sequence.
   #[inline]
-  pub fn next_u32(&mut self) -> u32 {
+  pub const fn next_u32(&mut self) -> u32 {
     let new_state = lcg64_step(PCG_MUL_64, self.inc, self.state);
     let out = xsh_rr_u64_to_u32(self.state);
     self.state = new_state;
@@ -74,9 +108,373 @@ impl PCG32 {
   /// Because the sequence is a loop, you can go "back" by `x` steps just by
   /// passing `x.wrapping_neg()` to go sufficiently far forward.
   #[inline]
-  pub fn jump(&mut self, delta: u64) {
+  pub const fn jump(&mut self, delta: u64) {
     self.state = lcg64_jump(PCG_MUL_64, self.inc, self.state, delta);
   }
+
+  /// Gets the `i`th of `n` equal sized blocks of this generator's sequence.
+  ///
+  /// The output sequence (starting from the current position) is divided into
+  /// `n` blocks that don't overlap, each of about `2**64 / n` outputs. The
+  /// generator returned is positioned at the start of block `i`, so as long as
+  /// each block's generator is used for less than `2**64 / n` outputs, no two
+  /// of them will ever give the same part of the sequence.
+  ///
+  /// Because each block's generator only depends on `self`, `i`, and `n`, the
+  /// work done with each block is the same no matter which thread does it, or
+  /// in what order.
+  ///
+  /// ## Panics
+  /// * If `n` is 0, or if `i` is not less than `n`.
+  #[inline]
+  #[must_use]
+  pub const fn substream(&self, i: u64, n: u64) -> Self {
+    assert!(i < n, "substream index out of bounds");
+    let block_len = (1_u128 << 64) / (n as u128);
+    let mut out = Self::new(self.state, self.inc);
+    out.jump((block_len * (i as u128)) as u64);
+    out
+  }
+
+  /// Splits this generator's sequence into `n` non-overlapping substreams.
+  ///
+  /// The iterator gives each [substream](Self::substream) in order.
+  ///
+  /// ```
+  /// # use randomize::{Gen32, PCG32};
+  /// let gen = PCG32::seed(5, 5);
+  /// let trials = |mut g: PCG32| (0..1000).map(|_| g.d6() as u64).sum::<u64>();
+  ///
+  /// let sequential: u64 = gen.split_streams(4).map(trials).sum();
+  /// let threaded: u64 = std::thread::scope(|s| {
+  ///   let handles: Vec<_> = gen.split_streams(4).map(|g| s.spawn(move || trials(g))).collect();
+  ///   handles.into_iter().map(|h| h.join().unwrap()).sum()
+  /// });
+  /// assert_eq!(sequential, threaded);
+  /// ```
+  ///
+  /// ## Panics
+  /// * If `n` is 0.
+  #[inline]
+  pub fn split_streams(&self, n: u64) -> impl Iterator<Item = Self> {
+    assert!(n > 0, "can't split into 0 streams");
+    let base = self.clone();
+    (0..n).map(move |i| base.substream(i, n))
+  }
+
+  /// Gives every `n`th output of this generator, starting with output `i`.
+  ///
+  /// Using `n` leapfrog generators with `i` in `0 .. n` will interleave to give
+  /// exactly the same outputs as this generator.
+  ///
+  /// ## Panics
+  /// * If `n` is 0, or if `i` is not less than `n`.
+  #[inline]
+  #[must_use]
+  pub const fn leapfrog(&self, i: u64, n: u64) -> Leapfrog {
+    assert!(i < n, "leapfrog index out of bounds");
+    // Note: Taking `n` steps of an LCG is the same as taking one step
+    // of another LCG. We find that other LCG's `mul` and `add` by jumping from
+    // the states 0 and 1.
+    let add = lcg64_jump(PCG_MUL_64, self.inc, 0, n);
+    let mul = lcg64_jump(PCG_MUL_64, self.inc, 1, n).wrapping_sub(add);
+    let state = lcg64_jump(PCG_MUL_64, self.inc, self.state, i);
+    Leapfrog { state, mul, add }
+  }
+}
+
+/// Every `n`th output of a [PCG32], made with [PCG32::leapfrog].
+///
+/// This is an iterator, and also a [Gen32](crate::Gen32).
+#[derive(Debug, Clone)]
+pub struct Leapfrog {
+  state: u64,
+  mul: u64,
+  add: u64,
+}
+impl Leapfrog {
+  /// Generate the next `u32` in the sequence.
+  #[inline]
+  pub const fn next_u32(&mut self) -> u32 {
+    let out = xsh_rr_u64_to_u32(self.state);
+    self.state = lcg64_step(self.mul, self.add, self.state);
+    out
+  }
+}
+impl Iterator for Leapfrog {
+  type Item = u32;
+  #[inline]
+  fn next(&mut self) -> Option<u32> {
+    Some(self.next_u32())
+  }
+}
+
+/// A [Permuted Congruential Generator][wp] with 64-bit output, using the
+/// "cheap multiplier" and the "DXSM" output function.
+///
+/// [wp]: https://en.wikipedia.org/wiki/Permuted_congruential_generator
+///
+/// This is the same generator as NumPy's `PCG64DXSM`. Given the same `state`
+/// and `inc` values it will produce the exact same output stream, so you can
+/// copy a NumPy generator's state into this type to replay a stream in Rust.
+///
+/// * Period: `2**128` when `inc` is odd, otherwise less
+#[derive(Debug, Clone)]
+pub struct PCG64DXSM {
+  /// The generator's state.
+  ///
+  /// This changes with each step of the generator. It's the generator's
+  /// "position" within the output stream.
+  pub state: u128,
+
+  /// The generator's increment.
+  ///
+  /// This doesn't change as the generator advances. Instead it determines which
+  /// of the possible output streams the generator will use. Each `inc` value
+  /// will give a different ordering of all the possible outputs.
+  pub inc: u128,
+}
+impl PCG64DXSM {
+  /// Creates a new generator by directly using the value given.
+  ///
+  /// This is how you'd load the `state` and `inc` of a NumPy generator (as
+  /// given by its `bit_generator.state` dictionary).
+  #[inline]
+  #[must_use]
+  pub const fn new(state: u128, inc: u128) -> Self {
+    Self { state, inc }
+  }
+
+  /// Seed a new generator.
+  ///
+  /// This follows `pcg_setseq_128_srandom_r` from the reference PCG code, which
+  /// steps with the full [PCG_MUL_128](crate::formulas::PCG_MUL_128) while
+  /// seeding. Only the steps after seeding use the cheap multiplier.
+  ///
+  /// To replay a stream from NumPy, copy its `state` and `inc` with
+  /// [new](Self::new) rather than trying to repeat its seeding.
+  #[inline]
+  pub const fn seed(seed: u128, inc: u128) -> Self {
+    let inc = (inc << 1) | 1;
+    let state = lcg128_step(PCG_MUL_128, inc, 0);
+    let state = lcg128_step(PCG_MUL_128, inc, state.wrapping_add(seed));
+    Self { state, inc }
+  }
+
+  /// Create a new generator seeded with data from
+  /// [getrandom](getrandom::getrandom).
+  ///
+  /// This method ensures that the `inc` of the new generator is odd.
+  #[cfg(feature = "getrandom")]
+  #[cfg_attr(docsrs, doc(cfg(feature = "getrandom")))]
+  #[inline]
+  pub fn from_getrandom() -> Result<Self, crate::Error> {
+    use bytemuck::bytes_of_mut;
+
+    let mut buf = [0_u128; 2];
+    getrandom::getrandom(bytes_of_mut(&mut buf))?;
+
+    Ok(Self::new(buf[0], buf[1] | 1))
+  }
+
+  /// Generate the next `u64` in the sequence.
+  #[inline]
+  pub const fn next_u64(&mut self) -> u64 {
+    let new_state = lcg128_step(PCG_CHEAP_MUL_128 as u128, self.inc, self.state);
+    let out = dxsm_u128_to_u64(self.state);
+    self.state = new_state;
+    out
+  }
+
+  /// Generate the next `u32` in the sequence.
+  ///
+  /// This is the upper half of the next `u64`. Note that this does *not* match
+  /// NumPy's `next_uint32`, which buffers both halves of each `u64` output.
+  #[inline]
+  pub const fn next_u32(&mut self) -> u32 {
+    (self.next_u64() >> 32) as u32
+  }
+
+  /// Jump the generator the given number of steps forward in the sequence.
+  ///
+  /// This can go `x` steps forward in only about `log2(x)` time.
+  ///
+  /// Because the sequence is a loop, you can go "back" by `x` steps just by
+  /// passing `x.wrapping_neg()` to go sufficiently far forward.
+  #[inline]
+  pub const fn jump(&mut self, delta: u128) {
+    self.state = lcg128_jump(PCG_CHEAP_MUL_128 as u128, self.inc, self.state, delta);
+  }
+}
+
+/// A [Permuted Congruential Generator][wp] with 32-bit output, using a
+/// multiplicative congruential generator as the base.
+///
+/// [wp]: https://en.wikipedia.org/wiki/Permuted_congruential_generator
+///
+/// This is like the [PCG32], but there's no `inc` value: each step is only a
+/// multiply. This makes each step slightly faster, at the cost of a shorter
+/// period and having only a single output stream. The `state` must always be
+/// odd, otherwise the generator will rapidly degrade to all 0 outputs.
+///
+/// The output uses the same `xsh_rr` function as [PCG32], making this the
+/// `mcg_64_xsh_rr_32` generator from the reference PCG implementation. Note
+/// that the reference `pcg32_fast` uses `xsh_rs` instead, so it gives a
+/// different stream.
+///
+/// * Period: `2**62`
+#[derive(Debug, Clone)]
+pub struct MCG32 {
+  /// The generator's state.
+  ///
+  /// This changes with each step of the generator. It's the generator's
+  /// "position" within the output stream. This should always be odd.
+  pub state: u64,
+}
+impl MCG32 {
+  /// Creates a new generator by directly using the value given.
+  ///
+  /// The lowest bit of `state` is forced to be 1, but otherwise the value is
+  /// used directly. If the initial `state` is not from a randomization source
+  /// then you should probably call [seed](Self::seed) instead.
+  #[inline]
+  #[must_use]
+  pub const fn new(state: u64) -> Self {
+    Self { state: state | 1 }
+  }
+
+  /// Seed a new generator.
+  #[inline]
+  pub const fn seed(seed: u64) -> Self {
+    let seed = (seed << 1) | 1;
+    let state = lcg64_step(PCG_MUL_64, 0, seed);
+    Self { state }
+  }
+
+  /// Create a new generator seeded with data from
+  /// [getrandom](getrandom::getrandom).
+  ///
+  /// This method ensures that the `state` of the new generator is odd.
+  #[cfg(feature = "getrandom")]
+  #[cfg_attr(docsrs, doc(cfg(feature = "getrandom")))]
+  #[inline]
+  pub fn from_getrandom() -> Result<Self, crate::Error> {
+    use bytemuck::bytes_of_mut;
+
+    let mut state = 0_u64;
+    getrandom::getrandom(bytes_of_mut(&mut state))?;
+
+    Ok(Self::new(state))
+  }
+
+  /// Generate the next `u32` in the sequence.
+  #[inline]
+  pub const fn next_u32(&mut self) -> u32 {
+    let new_state = lcg64_step(PCG_MUL_64, 0, self.state);
+    let out = xsh_rr_u64_to_u32(self.state);
+    self.state = new_state;
+    out
+  }
+
+  /// Jump the generator the given number of steps forward in the sequence.
+  ///
+  /// This can go `x` steps forward in only about `log2(x)` time.
+  ///
+  /// Because the sequence is a loop, you can go "back" by `x` steps just by
+  /// passing `x.wrapping_neg()` to go sufficiently far forward.
+  #[inline]
+  pub const fn jump(&mut self, delta: u64) {
+    self.state = lcg64_jump(PCG_MUL_64, 0, self.state, delta);
+  }
+}
+
+/// A [Permuted Congruential Generator][wp] with 64-bit output, using a
+/// multiplicative congruential generator as the base.
+///
+/// [wp]: https://en.wikipedia.org/wiki/Permuted_congruential_generator
+///
+/// This is the 128-bit state version of [MCG32]. The `state` must always be
+/// odd, otherwise the generator will rapidly degrade to all 0 outputs.
+///
+/// A PCG output function only gives out part of the bits of the state, so a
+/// 64-bit output needs a 128-bit state. Each step is a
+/// [lcg128_step](crate::formulas::lcg128_step) with
+/// [PCG_MUL_128](crate::formulas::PCG_MUL_128) and no add, and the output is
+/// [xsl_rr_u128_to_u64](crate::formulas::xsl_rr_u128_to_u64).
+///
+/// This is the same as the `pcg64_fast` generator from the reference PCG
+/// implementation. Like the reference, the output of each step is computed
+/// *after* the state is advanced.
+///
+/// * Period: `2**126`
+#[derive(Debug, Clone)]
+pub struct MCG64 {
+  /// The generator's state.
+  ///
+  /// This changes with each step of the generator. It's the generator's
+  /// "position" within the output stream. This should always be odd.
+  pub state: u128,
+}
+impl MCG64 {
+  /// Creates a new generator by directly using the value given.
+  ///
+  /// The lowest bit of `state` is forced to be 1, but otherwise the value is
+  /// used directly.
+  #[inline]
+  #[must_use]
+  pub const fn new(state: u128) -> Self {
+    Self { state: state | 1 }
+  }
+
+  /// Seed a new generator.
+  #[inline]
+  pub const fn seed(seed: u128) -> Self {
+    let seed = (seed << 1) | 1;
+    let state = lcg128_step(PCG_MUL_128, 0, seed);
+    Self { state }
+  }
+
+  /// Create a new generator seeded with data from
+  /// [getrandom](getrandom::getrandom).
+  ///
+  /// This method ensures that the `state` of the new generator is odd.
+  #[cfg(feature = "getrandom")]
+  #[cfg_attr(docsrs, doc(cfg(feature = "getrandom")))]
+  #[inline]
+  pub fn from_getrandom() -> Result<Self, crate::Error> {
+    use bytemuck::bytes_of_mut;
+
+    let mut state = 0_u128;
+    getrandom::getrandom(bytes_of_mut(&mut state))?;
+
+    Ok(Self::new(state))
+  }
+
+  /// Generate the next `u64` in the sequence.
+  #[inline]
+  pub const fn next_u64(&mut self) -> u64 {
+    self.state = lcg128_step(PCG_MUL_128, 0, self.state);
+    xsl_rr_u128_to_u64(self.state)
+  }
+
+  /// Generate the next `u32` in the sequence.
+  ///
+  /// This is the upper half of the next `u64`.
+  #[inline]
+  pub const fn next_u32(&mut self) -> u32 {
+    (self.next_u64() >> 32) as u32
+  }
+
+  /// Jump the generator the given number of steps forward in the sequence.
+  ///
+  /// This can go `x` steps forward in only about `log2(x)` time.
+  ///
+  /// Because the sequence is a loop, you can go "back" by `x` steps just by
+  /// passing `x.wrapping_neg()` to go sufficiently far forward.
+  #[inline]
+  pub const fn jump(&mut self, delta: u128) {
+    self.state = lcg128_jump(PCG_MUL_128, 0, self.state, delta);
+  }
 }
 
 /// A [Permuted Congruential Generator][wp] with 32-bit output, extended to `K`
@@ -133,6 +531,9 @@ impl<const K: usize> PCG32K<K> {
     let seed = (seed << 1) | 1;
     let state = lcg64_step(PCG_MUL_64, 1, seed);
     let mut i = 0;
+    // Note: This isn't `PCG_MUL_32`, it's the low bits of `PCG_MUL_64`. That's
+    // still a full period multiplier, and changing it would change the output
+    // of every already seeded generator.
     while i < K {
       ext[i] = lcg32_step(PCG_MUL_64 as u32, 1, ext[i]);
       i += 1;
@@ -140,6 +541,35 @@ impl<const K: usize> PCG32K<K> {
     Self { state, ext }
   }
 
+  /// Seed a new generator by hashing arbitrary bytes.
+  ///
+  /// Every bit of the input affects the `state` and every element of the `ext`
+  /// array of the new generator. This is a `const fn`, so it can be used to
+  /// make a generator constant.
+  #[inline]
+  #[must_use]
+  pub const fn seed_from_bytes(bytes: &[u8]) -> Self {
+    let h = hash_bytes64(bytes, 0);
+    let mut ext = [0_u32; K];
+    let mut i = 0;
+    while i < K {
+      ext[i] = (splitmix64(h, 1 + i as u64) >> 32) as u32;
+      i += 1;
+    }
+    Self::new(splitmix64(h, 0), ext)
+  }
+
+  /// Seed a new generator from a string, such as a seed phrase typed in by a
+  /// user.
+  ///
+  /// This is the same as [seed_from_bytes](Self::seed_from_bytes) with the
+  /// UTF-8 bytes of the string.
+  #[inline]
+  #[must_use]
+  pub const fn from_str_seed(s: &str) -> Self {
+    Self::seed_from_bytes(s.as_bytes())
+  }
+
   /// Create a new generator seeded with data from
   /// [getrandom](getrandom::getrandom).
   ///
@@ -149,7 +579,7 @@ impl<const K: usize> PCG32K<K> {
   #[cfg(feature = "getrandom")]
   #[cfg_attr(docsrs, doc(cfg(feature = "getrandom")))]
   #[inline]
-  pub fn from_getrandom() -> Result<Self, getrandom::Error> {
+  pub fn from_getrandom() -> Result<Self, crate::Error> {
     use bytemuck::bytes_of_mut;
 
     let mut state = 0_u64;
@@ -172,10 +602,11 @@ impl<const K: usize> PCG32K<K> {
   #[cfg(feature = "getrandom")]
   #[cfg_attr(docsrs, doc(cfg(feature = "getrandom")))]
   #[inline]
-  pub fn scramble_ext_array(&mut self) -> Result<(), getrandom::Error> {
+  pub fn scramble_ext_array(&mut self) -> Result<(), crate::Error> {
     use bytemuck::bytes_of_mut;
 
-    getrandom::getrandom(bytes_of_mut(&mut self.ext))
+    getrandom::getrandom(bytes_of_mut(&mut self.ext))?;
+    Ok(())
   }
 
   /// Generate the next `u32` in the sequence.
@@ -239,3 +670,142 @@ fn test_ext_add() {
   assert_eq!(x.ext[1], 0);
   assert_eq!(x.ext[2], 1);
 }
+
+#[test]
+fn test_pcg64dxsm_known_answers() {
+  // Generated by the reference C code (`pcg_setseq_128_srandom_r`, then the
+  // cheap multiplier step and DXSM output of `pcg_cm_random_r`).
+  let mut x = PCG64DXSM::seed(42, 54);
+  assert_eq!(x.state, 0xde2bce05be013be3d3f6c45a41e54320);
+  assert_eq!(x.inc, 0x6d);
+  for expected in [
+    0x8bc04bdf82aa0b82_u64,
+    0x3558b9abc6e2724d,
+    0x6751d12e0ca3151b,
+    0xe06216c906e1e1d5,
+    0x123940894f22bb62,
+    0xf15839e2a3d83136,
+  ] {
+    assert_eq!(x.next_u64(), expected);
+  }
+  //
+  let mut x =
+    PCG64DXSM::seed(0x0123456789abcdef_fedcba9876543210, 0x1111111122222222_3333333344444444);
+  for expected in [
+    0x40700cf146fea9ef_u64,
+    0x7f7fc440337b5bc9,
+    0x08c7dc4fc72f93f1,
+    0x2f0fd3240853c602,
+    0x2e1142ad4fed6fa2,
+    0xbb993eb3d30bf9e1,
+  ] {
+    assert_eq!(x.next_u64(), expected);
+  }
+}
+
+#[test]
+fn test_pcg64dxsm_jump() {
+  let mut x = PCG64DXSM::seed(42, 54);
+  let mut y = x.clone();
+  for _ in 0..100 {
+    x.next_u64();
+  }
+  y.jump(100);
+  assert_eq!(x.state, y.state);
+  y.jump(100_u128.wrapping_neg());
+  assert_eq!(y.state, PCG64DXSM::seed(42, 54).state);
+}
+
+#[test]
+fn test_mcg_known_answers() {
+  // Generated by the reference C code (`pcg_mcg_64_xsh_rr_32_random_r` and
+  // `pcg64_fast_random_r`, each after seeding with an `initstate` of 42).
+  let mut x = MCG32::new(42);
+  for expected in [0x00000000_u32, 0x21b756ee, 0x135e80e8, 0xf6025706, 0xd2fc74a3, 0x157c82ab] {
+    assert_eq!(x.next_u32(), expected);
+  }
+  //
+  let mut x = MCG64::new(42);
+  for expected in [
+    0x63b4a3a813ce700a_u64,
+    0x382954200617ab24,
+    0xa7fd85ae3fe950ce,
+    0xd715286aa2887737,
+    0x60c92fee2e59f32c,
+    0x84c4e96beff30017,
+  ] {
+    assert_eq!(x.next_u64(), expected);
+  }
+}
+
+#[test]
+fn test_mcg_jump() {
+  let mut x = MCG32::seed(5);
+  let mut y = x.clone();
+  for _ in 0..100 {
+    x.next_u32();
+  }
+  y.jump(100);
+  assert_eq!(x.state, y.state);
+  y.jump(100_u64.wrapping_neg());
+  assert_eq!(y.state, MCG32::seed(5).state);
+  //
+  let mut x = MCG64::seed(5);
+  let mut y = x.clone();
+  for _ in 0..100 {
+    x.next_u64();
+  }
+  y.jump(100);
+  assert_eq!(x.state, y.state);
+  y.jump(100_u128.wrapping_neg());
+  assert_eq!(y.state, MCG64::seed(5).state);
+}
+
+#[test]
+fn test_seed_from_bytes() {
+  const A: PCG32 = PCG32::from_str_seed("world-42");
+  let b = PCG32::seed_from_bytes(b"world-42");
+  assert_eq!(A.state, b.state);
+  assert_eq!(A.inc, b.inc);
+  assert_eq!(A.inc % 2, 1);
+  // Small changes in the input give unrelated generators.
+  for other in ["world-43", "World-42", "world-42 ", "world-42\0", ""] {
+    let c = PCG32::from_str_seed(other);
+    assert_ne!(A.state, c.state);
+    assert_ne!(A.inc, c.inc);
+  }
+  //
+  const K: PCG32K<4> = PCG32K::from_str_seed("world-42");
+  assert_eq!(K.state, A.state);
+  assert!(K.ext.iter().all(|&x| x != 0));
+  assert_ne!(K.ext[0], K.ext[1]);
+}
+
+#[test]
+fn test_substreams() {
+  let g = PCG32::seed(1, 2);
+  let streams: [PCG32; 3] = {
+    let mut it = g.split_streams(3);
+    core::array::from_fn(|_| it.next().unwrap())
+  };
+  assert_eq!(streams[0].state, g.state);
+  // Each stream starts where the one before it would end.
+  let mut x = streams[0].clone();
+  x.jump(((1_u128 << 64) / 3) as u64);
+  assert_eq!(x.state, streams[1].state);
+  x.jump(((1_u128 << 64) / 3) as u64);
+  assert_eq!(x.state, streams[2].state);
+  // The last block is also correct for powers of two.
+  let mut x = g.clone();
+  x.jump(3 << 62);
+  assert_eq!(x.state, g.substream(3, 4).state);
+}
+
+#[test]
+fn test_leapfrog() {
+  let mut g = PCG32::seed(1, 2);
+  let mut frogs = [g.leapfrog(0, 3), g.leapfrog(1, 3), g.leapfrog(2, 3)];
+  for i in 0..30 {
+    assert_eq!(g.next_u32(), frogs[i % 3].next_u32());
+  }
+}
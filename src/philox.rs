@@ -0,0 +1,261 @@
+//! Counter-based generation.
+
+const PHILOX_M0: u32 = 0xD2511F53;
+const PHILOX_M1: u32 = 0xCD9E8D57;
+const PHILOX_W0: u32 = 0x9E3779B9;
+const PHILOX_W1: u32 = 0xBB67AE85;
+
+/// The [Philox4x32-10][random123] counter-based generator.
+///
+/// [random123]: https://www.thesalmons.org/john/random123/
+///
+/// Unlike the [PCG32](crate::PCG32), this generator has no sequential state. Each output
+/// block is a pure function of a `key` and a `counter`, so any position in the
+/// output stream can be computed directly, in constant time, with
+/// [value_at](Self::value_at) or [block](Self::block). This makes it well
+/// suited for parallel work where each work item needs its own random values
+/// and you don't want to pass a generator around.
+///
+/// When used as a normal generator, the counter is incremented after each
+/// block of four outputs.
+///
+/// * Period: `2**130` outputs per `key`
+#[derive(Debug, Clone)]
+pub struct Philox4x32 {
+  /// The generator's key.
+  ///
+  /// This doesn't change as the generator advances. Each `key` value gives an
+  /// unrelated output stream.
+  pub key: [u32; 2],
+
+  /// The generator's counter.
+  ///
+  /// This is the index of the next block of four outputs.
+  pub counter: u128,
+
+  /// The current block of outputs.
+  block: [u32; 4],
+
+  /// The index of the next output within `block`, or 4 if the block is used up.
+  index: usize,
+}
+impl Philox4x32 {
+  /// Creates a new generator with the given key, starting at counter 0.
+  #[inline]
+  #[must_use]
+  pub const fn new(key: [u32; 2]) -> Self {
+    Self::with_counter(key, 0)
+  }
+
+  /// Creates a new generator with the given key and counter.
+  #[inline]
+  #[must_use]
+  pub const fn with_counter(key: [u32; 2], counter: u128) -> Self {
+    Self { key, counter, block: [0; 4], index: 4 }
+  }
+
+  /// Create a new generator with a key from [getrandom](getrandom::getrandom).
+  #[cfg(feature = "getrandom")]
+  #[cfg_attr(docsrs, doc(cfg(feature = "getrandom")))]
+  #[inline]
+  pub fn from_getrandom() -> Result<Self, crate::Error> {
+    use bytemuck::bytes_of_mut;
+
+    let mut key = [0_u32; 2];
+    getrandom::getrandom(bytes_of_mut(&mut key))?;
+
+    Ok(Self::new(key))
+  }
+
+  /// Computes the block of four outputs for a given key and counter.
+  ///
+  /// The counter's lowest 32 bits are the first element of the array, and so
+  /// on.
+  #[inline]
+  #[must_use]
+  pub const fn block(mut key: [u32; 2], mut ctr: [u32; 4]) -> [u32; 4] {
+    let mut round = 0;
+    while round < 10 {
+      if round > 0 {
+        key[0] = key[0].wrapping_add(PHILOX_W0);
+        key[1] = key[1].wrapping_add(PHILOX_W1);
+      }
+      let p0 = (PHILOX_M0 as u64) * (ctr[0] as u64);
+      let p1 = (PHILOX_M1 as u64) * (ctr[2] as u64);
+      let (hi0, lo0) = ((p0 >> 32) as u32, p0 as u32);
+      let (hi1, lo1) = ((p1 >> 32) as u32, p1 as u32);
+      ctr = [hi1 ^ ctr[1] ^ key[0], lo1, hi0 ^ ctr[3] ^ key[1], lo0];
+      round += 1;
+    }
+    ctr
+  }
+
+  /// Gives the output at the given index of the output stream for a key.
+  ///
+  /// This is the same as the output that a generator with the given key would
+  /// produce after `index` calls to `next_u32`.
+  ///
+  /// A `u128` index only reaches the first `2**128` outputs of the stream. To
+  /// get outputs past that, use [block](Self::block) with a counter of `2**126`
+  /// or more.
+  #[inline]
+  #[must_use]
+  pub const fn value_at(key: [u32; 2], index: u128) -> u32 {
+    let out = Self::block(key, split_counter(index >> 2));
+    out[(index & 0b11) as usize]
+  }
+
+  /// Generate the next `u32` in the sequence.
+  #[inline]
+  pub const fn next_u32(&mut self) -> u32 {
+    if self.index >= 4 {
+      self.block = Self::block(self.key, split_counter(self.counter));
+      self.counter = self.counter.wrapping_add(1);
+      self.index = 0;
+    }
+    let out = self.block[self.index];
+    self.index += 1;
+    out
+  }
+
+  /// The counter of the block that the next output comes from, and the index
+  /// of the next output within that block.
+  ///
+  /// This is the full position within the output stream, and it can be given
+  /// back to [set_block_position](Self::set_block_position).
+  #[inline]
+  #[must_use]
+  pub const fn block_position(&self) -> (u128, usize) {
+    // Note: If the current block isn't computed yet, we're at the start of the
+    // block that `counter` points to. Otherwise `counter` was already moved
+    // past the current block.
+    if self.index >= 4 {
+      (self.counter, 0)
+    } else {
+      (self.counter.wrapping_sub(1), self.index)
+    }
+  }
+
+  /// Moves the generator to the given block counter and index within the
+  /// block.
+  ///
+  /// The `index` is taken modulo 4.
+  #[inline]
+  pub const fn set_block_position(&mut self, counter: u128, index: usize) {
+    self.counter = counter;
+    self.index = 4;
+    let skip = index & 0b11;
+    if skip > 0 {
+      self.block = Self::block(self.key, split_counter(self.counter));
+      self.counter = self.counter.wrapping_add(1);
+      self.index = skip;
+    }
+  }
+
+  /// The index of the next output within the output stream, modulo `2**128`.
+  ///
+  /// This is the `index` that you'd pass to [value_at](Self::value_at) to get
+  /// the same value that the next call to `next_u32` will return. Once the
+  /// counter reaches `2**126` this no longer fits in a `u128`, so use
+  /// [block_position](Self::block_position) if you need the full position.
+  #[inline]
+  #[must_use]
+  pub const fn position(&self) -> u128 {
+    let (counter, index) = self.block_position();
+    (counter << 2) | (index as u128)
+  }
+
+  /// Jump the generator the given number of steps forward in the sequence.
+  ///
+  /// Unlike with the [PCG32](crate::PCG32), this takes constant time.
+  ///
+  /// The period is larger than any `u128` delta, so passing a wrapped negative
+  /// value doesn't go back. Use [jump_back](Self::jump_back) for that.
+  #[inline]
+  pub const fn jump(&mut self, delta: u128) {
+    let (counter, index) = self.block_position();
+    let index = index as u128 + (delta & 0b11);
+    let counter = counter.wrapping_add(delta >> 2).wrapping_add(index >> 2);
+    self.set_block_position(counter, (index & 0b11) as usize);
+  }
+
+  /// Jump the generator the given number of steps backward in the sequence.
+  ///
+  /// This also takes constant time.
+  #[inline]
+  pub const fn jump_back(&mut self, delta: u128) {
+    let (counter, index) = self.block_position();
+    let index = index as u128;
+    let back = delta & 0b11;
+    let borrow = (back > index) as u128;
+    let counter = counter.wrapping_sub(delta >> 2).wrapping_sub(borrow);
+    self.set_block_position(counter, (index.wrapping_sub(back) & 0b11) as usize);
+  }
+}
+
+#[inline]
+#[must_use]
+const fn split_counter(ctr: u128) -> [u32; 4] {
+  [ctr as u32, (ctr >> 32) as u32, (ctr >> 64) as u32, (ctr >> 96) as u32]
+}
+
+#[test]
+fn test_philox_known_answers() {
+  // From the `kat_vectors` file of the Random123 distribution.
+  assert_eq!(
+    Philox4x32::block([0, 0], [0, 0, 0, 0]),
+    [0x6627e8d5, 0xe169c58d, 0xbc57ac4c, 0x9b00dbd8]
+  );
+  assert_eq!(
+    Philox4x32::block([u32::MAX; 2], [u32::MAX; 4]),
+    [0x408f276d, 0x41c83b0e, 0xa20bc7c6, 0x6d5451fd]
+  );
+  assert_eq!(
+    Philox4x32::block([0xa4093822, 0x299f31d0], [0x243f6a88, 0x85a308d3, 0x13198a2e, 0x03707344]),
+    [0xd16cfe09, 0x94fdcceb, 0x5001e420, 0x24126ea1]
+  );
+}
+
+#[test]
+fn test_philox_value_at_and_jump() {
+  let key = [1, 2];
+  let mut x = Philox4x32::new(key);
+  for i in 0..50 {
+    assert_eq!(x.next_u32(), Philox4x32::value_at(key, i));
+  }
+  for delta in [0, 1, 2, 3, 4, 5, 7, 100] {
+    let mut y = x.clone();
+    y.jump(delta);
+    assert_eq!(y.next_u32(), Philox4x32::value_at(key, 50 + delta));
+  }
+  x.jump_back(10);
+  assert_eq!(x.next_u32(), Philox4x32::value_at(key, 40));
+  x.jump_back(41);
+  assert_eq!(x.position(), 0);
+}
+
+#[test]
+fn test_philox_high_counter() {
+  let key = [3, 4];
+  let last = Philox4x32::block(key, [u32::MAX; 4]);
+  let first = Philox4x32::block(key, [0; 4]);
+  //
+  let mut x = Philox4x32::with_counter(key, 1 << 127);
+  x.jump(0);
+  assert_eq!(x.block_position(), (1 << 127, 0));
+  //
+  let mut x = Philox4x32::with_counter(key, u128::MAX);
+  x.jump(2);
+  assert_eq!(x.block_position(), (u128::MAX, 2));
+  assert_eq!([x.next_u32(), x.next_u32()], [last[2], last[3]]);
+  assert_eq!(x.block_position(), (0, 0));
+  assert_eq!(x.next_u32(), first[0]);
+  x.jump_back(3);
+  assert_eq!(x.block_position(), (u128::MAX, 2));
+  assert_eq!(x.next_u32(), last[2]);
+  //
+  let mut y = Philox4x32::new(key);
+  y.jump_back(1);
+  assert_eq!(y.block_position(), (u128::MAX, 3));
+  assert_eq!(y.next_u32(), last[3]);
+}
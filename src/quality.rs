@@ -0,0 +1,387 @@
+//! Statistical tests for checking the output quality of a generator.
+//!
+//! These are quick sanity checks, meant for things like a new [Gen32]
+//! implementation or an unusual [PCG32K](crate::PCG32K) size. Passing them
+//! doesn't prove that a generator is good, but failing them means that
+//! something is badly wrong. For serious analysis use a full battery such as
+//! PractRand or TestU01.
+//!
+//! Each test gives a [TestOutcome] with a p-value. A result is considered a
+//! failure when the p-value is below the [alpha](QualityConfig::alpha) of the
+//! config. With many tests run, an occasional small p-value is expected even
+//! from a good generator, so re-run a failure with another seed before
+//! drawing any conclusions.
+//!
+//! ```
+//! use randomize::{quality::*, PCG32};
+//! let config = QualityConfig { samples: 1 << 14, ..QualityConfig::default() };
+//! for outcome in run_suite(&mut PCG32::seed(1, 1), &config) {
+//!   assert!(outcome.passed, "{outcome:?}");
+//! }
+//! ```
+
+use crate::{BoundedRandU16, BoundedRandU32, Gen32};
+use std::{vec, vec::Vec};
+
+/// Settings for the quality tests.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct QualityConfig {
+  /// The number of values each test draws from the generator.
+  pub samples: usize,
+  /// The p-value below which a test is considered failed.
+  pub alpha: f64,
+}
+impl Default for QualityConfig {
+  #[inline]
+  fn default() -> Self {
+    Self { samples: 1 << 20, alpha: 1e-4 }
+  }
+}
+
+/// The result of a single test.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TestOutcome {
+  /// The name of the test.
+  pub name: &'static str,
+  /// The test statistic.
+  pub statistic: f64,
+  /// The probability of a statistic at least this extreme from a good
+  /// generator.
+  pub p_value: f64,
+  /// If the p-value is at least the config's alpha.
+  pub passed: bool,
+}
+impl TestOutcome {
+  fn new(name: &'static str, statistic: f64, p_value: f64, config: &QualityConfig) -> Self {
+    Self { name, statistic, p_value, passed: p_value >= config.alpha }
+  }
+}
+
+/// Runs every test of this module.
+///
+/// The bounded tests use [d6](Gen32::d6)'s range and a `u32` range of 1000,
+/// and the float tests use [next_f32_unit](Gen32::next_f32_unit).
+#[inline]
+pub fn run_suite<G: Gen32>(gen: &mut G, config: &QualityConfig) -> Vec<TestOutcome> {
+  vec![
+    chi_squared_u16(gen, BoundedRandU16::_6, config),
+    chi_squared_u32(gen, BoundedRandU32::new(1000), config),
+    kolmogorov_smirnov_f32(gen, config),
+    gap(gen, config),
+    birthday_spacings(gen, config),
+    runs_up_down(gen, config),
+    serial_correlation(gen, config),
+  ]
+}
+
+/// Pearson's chi-squared test of a bounded `u16` range.
+///
+/// Values are sampled from the high half of each `u32`, the same way as the
+/// [Gen32] dice methods.
+#[inline]
+pub fn chi_squared_u16<G: Gen32>(
+  gen: &mut G, bounded: BoundedRandU16, config: &QualityConfig,
+) -> TestOutcome {
+  let mut counts = vec![0_u64; usize::from(bounded.count())];
+  for _ in 0..config.samples {
+    counts[usize::from(bounded.sample(|| (gen.next_u32() >> 16) as u16))] += 1;
+  }
+  chi_squared_uniform("chi-squared u16", &counts, config)
+}
+
+/// Pearson's chi-squared test of a bounded `u32` range.
+///
+/// ## Panics
+/// * If the range has more than `2**24` possible outputs.
+#[inline]
+pub fn chi_squared_u32<G: Gen32>(
+  gen: &mut G, bounded: BoundedRandU32, config: &QualityConfig,
+) -> TestOutcome {
+  assert!(bounded.count() <= 1 << 24, "too many outputs for a chi-squared test");
+  let mut counts = vec![0_u64; bounded.count() as usize];
+  for _ in 0..config.samples {
+    counts[bounded.sample(|| gen.next_u32()) as usize] += 1;
+  }
+  chi_squared_uniform("chi-squared u32", &counts, config)
+}
+
+/// The Kolmogorov-Smirnov test of [next_f32_unit](Gen32::next_f32_unit)
+/// against the uniform distribution.
+#[inline]
+pub fn kolmogorov_smirnov_f32<G: Gen32>(gen: &mut G, config: &QualityConfig) -> TestOutcome {
+  let mut xs: Vec<f64> = (0..config.samples).map(|_| f64::from(gen.next_f32_unit())).collect();
+  xs.sort_unstable_by(f64::total_cmp);
+  let n = xs.len() as f64;
+  let d = xs.iter().enumerate().fold(0.0_f64, |d, (i, &x)| {
+    let i = i as f64;
+    d.max((i + 1.0) / n - x).max(x - i / n)
+  });
+  let sqrt_n = n.sqrt();
+  let p = kolmogorov_sf((sqrt_n + 0.12 + 0.11 / sqrt_n) * d);
+  TestOutcome::new("kolmogorov-smirnov f32", d, p, config)
+}
+
+/// The gap test of [next_f32_unit](Gen32::next_f32_unit).
+///
+/// This is a chi-squared test of how many values fall outside of `0.0 .. 0.5`
+/// in between the values that fall inside of it.
+#[inline]
+pub fn gap<G: Gen32>(gen: &mut G, config: &QualityConfig) -> TestOutcome {
+  // gaps of `MAX_GAP` or longer all go in the last bucket.
+  const MAX_GAP: usize = 10;
+  let mut counts = [0_u64; MAX_GAP + 1];
+  let mut len = 0;
+  for _ in 0..config.samples {
+    if gen.next_f32_unit() < 0.5 {
+      counts[len.min(MAX_GAP)] += 1;
+      len = 0;
+    } else {
+      len += 1;
+    }
+  }
+  let total: u64 = counts.iter().sum();
+  let mut stat = 0.0;
+  for (r, &count) in counts.iter().enumerate() {
+    let prob = if r < MAX_GAP { 0.5_f64.powi(r as i32 + 1) } else { 0.5_f64.powi(MAX_GAP as i32) };
+    let expected = total as f64 * prob;
+    stat += (count as f64 - expected).powi(2) / expected;
+  }
+  TestOutcome::new("gap", stat, chi_squared_sf(stat, MAX_GAP as f64), config)
+}
+
+/// Marsaglia's birthday spacings test.
+///
+/// Each round picks 512 "birthdays" from the high 24 bits of each `u32`, then
+/// counts the repeated values among the sorted spacings between them. The
+/// total count over all rounds should follow a Poisson distribution.
+#[inline]
+pub fn birthday_spacings<G: Gen32>(gen: &mut G, config: &QualityConfig) -> TestOutcome {
+  const BIRTHDAYS: usize = 512;
+  const DAY_BITS: u32 = 24;
+  let rounds = (config.samples / BIRTHDAYS).max(1);
+  let mut days = [0_u32; BIRTHDAYS];
+  let mut repeats = 0_u64;
+  for _ in 0..rounds {
+    days.iter_mut().for_each(|d| *d = gen.next_u32() >> (32 - DAY_BITS));
+    days.sort_unstable();
+    let mut prev = 0;
+    for d in &mut days {
+      (*d, prev) = (*d - prev, *d);
+    }
+    days.sort_unstable();
+    repeats += days.windows(2).filter(|w| w[0] == w[1]).count() as u64;
+  }
+  let lambda = (BIRTHDAYS as f64).powi(3) / (4.0 * (1_u64 << DAY_BITS) as f64);
+  let p = poisson_two_sided(repeats, lambda * rounds as f64);
+  TestOutcome::new("birthday spacings", repeats as f64, p, config)
+}
+
+/// The runs up and down test of raw `u32` values.
+///
+/// Counts the runs of increasing and decreasing values, and compares that to
+/// the count expected of a random sequence.
+#[inline]
+pub fn runs_up_down<G: Gen32>(gen: &mut G, config: &QualityConfig) -> TestOutcome {
+  let n = config.samples.max(3);
+  let mut runs = 1_u64;
+  let mut prev = gen.next_u32();
+  let mut prev_up = None;
+  for _ in 1..n {
+    let x = gen.next_u32();
+    let up = x > prev;
+    if prev_up.is_some_and(|p| p != up) {
+      runs += 1;
+    }
+    prev_up = Some(up);
+    prev = x;
+  }
+  let n = n as f64;
+  let mean = (2.0 * n - 1.0) / 3.0;
+  let var = (16.0 * n - 29.0) / 90.0;
+  let z = (runs as f64 - mean) / var.sqrt();
+  TestOutcome::new("runs up and down", z, normal_two_sided(z), config)
+}
+
+/// Tests for correlation between each value of
+/// [next_f32_unit](Gen32::next_f32_unit) and the next one.
+#[inline]
+pub fn serial_correlation<G: Gen32>(gen: &mut G, config: &QualityConfig) -> TestOutcome {
+  let n = config.samples.max(2);
+  let first = f64::from(gen.next_f32_unit());
+  let (mut sum, mut sum_sq, mut sum_pairs) = (first, first * first, 0.0);
+  let mut prev = first;
+  for _ in 1..n {
+    let x = f64::from(gen.next_f32_unit());
+    sum += x;
+    sum_sq += x * x;
+    sum_pairs += prev * x;
+    prev = x;
+  }
+  // Knuth's circular form: the last value is paired with the first.
+  sum_pairs += prev * first;
+  let n = n as f64;
+  let r = (n * sum_pairs - sum * sum) / (n * sum_sq - sum * sum);
+  let z = r * n.sqrt();
+  TestOutcome::new("serial correlation", r, normal_two_sided(z), config)
+}
+
+fn chi_squared_uniform(name: &'static str, counts: &[u64], config: &QualityConfig) -> TestOutcome {
+  let total: u64 = counts.iter().sum();
+  let expected = total as f64 / counts.len() as f64;
+  let stat: f64 = counts.iter().map(|&c| (c as f64 - expected).powi(2) / expected).sum();
+  let df = (counts.len() - 1) as f64;
+  let p = if df > 0.0 { chi_squared_sf(stat, df) } else { 1.0 };
+  TestOutcome::new(name, stat, p, config)
+}
+
+/// `P(X >= stat)` for a chi-squared distribution.
+fn chi_squared_sf(stat: f64, df: f64) -> f64 {
+  gamma_q(df / 2.0, stat / 2.0)
+}
+
+/// `P(|Z| >= |z|)` for a standard normal distribution.
+fn normal_two_sided(z: f64) -> f64 {
+  gamma_q(0.5, z * z / 2.0)
+}
+
+/// Twice the smaller tail of a Poisson distribution, at most 1.
+fn poisson_two_sided(k: u64, mean: f64) -> f64 {
+  // `P(X <= k)` is `Q(k + 1, mean)`, and `P(X >= k)` is `P(k, mean)`.
+  let lower = gamma_q(k as f64 + 1.0, mean);
+  let upper = if k == 0 { 1.0 } else { 1.0 - gamma_q(k as f64, mean) };
+  (2.0 * lower.min(upper)).min(1.0)
+}
+
+/// `P(K >= lambda)` for the Kolmogorov distribution.
+fn kolmogorov_sf(lambda: f64) -> f64 {
+  use core::f64::consts::PI;
+  if lambda < 0.2 {
+    1.0
+  } else if lambda < 1.18 {
+    let y = -PI * PI / (8.0 * lambda * lambda);
+    let s: f64 = (1..=6).map(|k| (y * f64::from(2 * k - 1).powi(2)).exp()).sum();
+    1.0 - (2.0 * PI).sqrt() / lambda * s
+  } else {
+    let s: f64 = (1..=100)
+      .map(|k| {
+        let sign = if k % 2 == 1 { 1.0 } else { -1.0 };
+        sign * (-2.0 * f64::from(k * k) * lambda * lambda).exp()
+      })
+      .sum();
+    (2.0 * s).clamp(0.0, 1.0)
+  }
+}
+
+/// The regularized upper incomplete gamma function, `Q(a, x)`.
+fn gamma_q(a: f64, x: f64) -> f64 {
+  if x <= 0.0 {
+    return 1.0;
+  }
+  let log_prefix = a * x.ln() - x - ln_gamma(a);
+  if x < a + 1.0 {
+    // series for the lower function
+    let (mut term, mut sum, mut ap) = (1.0 / a, 1.0 / a, a);
+    for _ in 0..1000 {
+      ap += 1.0;
+      term *= x / ap;
+      sum += term;
+      if term.abs() < sum.abs() * 1e-15 {
+        break;
+      }
+    }
+    (1.0 - sum * log_prefix.exp()).max(0.0)
+  } else {
+    // Lentz's method for the continued fraction of the upper function
+    const TINY: f64 = 1e-300;
+    let mut b = x + 1.0 - a;
+    let mut c = 1.0 / TINY;
+    let mut d = 1.0 / b;
+    let mut h = d;
+    for i in 1..1000 {
+      let an = -f64::from(i) * (f64::from(i) - a);
+      b += 2.0;
+      d = an * d + b;
+      if d.abs() < TINY {
+        d = TINY;
+      }
+      c = b + an / c;
+      if c.abs() < TINY {
+        c = TINY;
+      }
+      d = 1.0 / d;
+      let delta = d * c;
+      h *= delta;
+      if (delta - 1.0).abs() < 1e-15 {
+        break;
+      }
+    }
+    h * log_prefix.exp()
+  }
+}
+
+/// The log of the gamma function, for `x > 0`.
+fn ln_gamma(x: f64) -> f64 {
+  // Lanczos approximation with g=7, n=9
+  const COEFFS: [f64; 9] = [
+    0.999_999_999_999_809_9,
+    676.520_368_121_885_1,
+    -1_259.139_216_722_402_8,
+    771.323_428_777_653_1,
+    -176.615_029_162_140_6,
+    12.507_343_278_686_905,
+    -0.138_571_095_265_720_12,
+    9.984_369_578_019_572e-6,
+    1.505_632_735_149_311_6e-7,
+  ];
+  if x < 0.5 {
+    use core::f64::consts::PI;
+    return (PI / (PI * x).sin()).ln() - ln_gamma(1.0 - x);
+  }
+  let x = x - 1.0;
+  let t = x + 7.5;
+  let s = COEFFS[1..].iter().enumerate().fold(COEFFS[0], |s, (i, &c)| s + c / (x + i as f64 + 1.0));
+  0.5 * (2.0 * core::f64::consts::PI).ln() + (x + 0.5) * t.ln() - t + s.ln()
+}
+
+#[test]
+fn test_distribution_functions() {
+  let close = |a: f64, b: f64| (a - b).abs() < 1e-6;
+  assert!(close(ln_gamma(5.0), 24.0_f64.ln()));
+  assert!(close(ln_gamma(0.5), core::f64::consts::PI.sqrt().ln()));
+  // chi-squared with 2 degrees of freedom has `sf(x) = exp(-x/2)`
+  assert!(close(chi_squared_sf(3.0, 2.0), (-1.5_f64).exp()));
+  assert!(close(chi_squared_sf(30.0, 2.0), (-15.0_f64).exp()));
+  assert!(close(normal_two_sided(1.959_963_984_540_054), 0.05));
+  assert!(close(kolmogorov_sf(1.358_099_0), 0.05));
+  assert!(close(kolmogorov_sf(1.0), 0.269_999_7));
+}
+
+#[test]
+fn test_suite_passes_good_generators() {
+  use crate::{PCG32, PCG32K};
+  let config = QualityConfig { samples: 1 << 16, alpha: 1e-6 };
+  for outcome in run_suite(&mut PCG32::seed(1, 2), &config)
+    .into_iter()
+    .chain(run_suite(&mut PCG32K::<3>::seed(3, [4, 5, 6]), &config))
+  {
+    assert!(outcome.passed, "{outcome:?}");
+  }
+}
+
+#[test]
+fn test_suite_fails_bad_generators() {
+  let config = QualityConfig { samples: 1 << 16, alpha: 1e-6 };
+
+  /// counts up by a large odd step, which is uniform over a full period but
+  /// very predictable.
+  struct Weyl(u32);
+  impl Gen32 for Weyl {
+    fn next_u32(&mut self) -> u32 {
+      self.0 = self.0.wrapping_add(0x9E37_79B9);
+      self.0
+    }
+  }
+  let failed =
+    run_suite(&mut Weyl(0), &config).into_iter().filter(|outcome| !outcome.passed).count();
+  assert!(failed >= 2, "only {failed} tests failed");
+}
@@ -0,0 +1,139 @@
+use crate::{
+  formulas::{lcg64_step, PCG_INC_64, PCG_MUL_64},
+  Gen32, PCG32, PCG32K,
+};
+use rand_core::{impls, RngCore, SeedableRng};
+
+impl RngCore for PCG32 {
+  #[inline]
+  fn next_u32(&mut self) -> u32 {
+    PCG32::next_u32(self)
+  }
+  #[inline]
+  fn next_u64(&mut self) -> u64 {
+    impls::next_u64_via_u32(self)
+  }
+  #[inline]
+  fn fill_bytes(&mut self, dst: &mut [u8]) {
+    impls::fill_bytes_via_next(self, dst)
+  }
+}
+
+impl SeedableRng for PCG32 {
+  /// The first 8 bytes are the `state`, the next 8 bytes are the `inc`, both
+  /// in little-endian order.
+  type Seed = [u8; 16];
+
+  /// This method ensures that the `inc` of the new generator is odd.
+  #[inline]
+  fn from_seed(seed: Self::Seed) -> Self {
+    let [s0, s1, s2, s3, s4, s5, s6, s7, i0, i1, i2, i3, i4, i5, i6, i7] = seed;
+    let state = u64::from_le_bytes([s0, s1, s2, s3, s4, s5, s6, s7]);
+    let inc = u64::from_le_bytes([i0, i1, i2, i3, i4, i5, i6, i7]);
+    Self::new(state, inc | 1)
+  }
+
+  #[inline]
+  fn seed_from_u64(state: u64) -> Self {
+    Self::seed(state, state)
+  }
+}
+
+impl<const K: usize> RngCore for PCG32K<K> {
+  #[inline]
+  fn next_u32(&mut self) -> u32 {
+    PCG32K::<K>::next_u32(self)
+  }
+  #[inline]
+  fn next_u64(&mut self) -> u64 {
+    impls::next_u64_via_u32(self)
+  }
+  #[inline]
+  fn fill_bytes(&mut self, dst: &mut [u8]) {
+    impls::fill_bytes_via_next(self, dst)
+  }
+}
+
+/// The seed type for using a [PCG32K] as a [SeedableRng].
+///
+/// This holds the extension array, in little-endian order. The generator's
+/// `state` is derived by hashing the extension array.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct PCG32KSeed<const K: usize>(pub [u32; K]);
+impl<const K: usize> Default for PCG32KSeed<K> {
+  #[inline]
+  fn default() -> Self {
+    Self([0; K])
+  }
+}
+impl<const K: usize> AsRef<[u8]> for PCG32KSeed<K> {
+  #[inline]
+  fn as_ref(&self) -> &[u8] {
+    bytemuck::cast_slice(&self.0)
+  }
+}
+impl<const K: usize> AsMut<[u8]> for PCG32KSeed<K> {
+  #[inline]
+  fn as_mut(&mut self) -> &mut [u8] {
+    bytemuck::cast_slice_mut(&mut self.0)
+  }
+}
+
+impl<const K: usize> SeedableRng for PCG32K<K> {
+  type Seed = PCG32KSeed<K>;
+
+  #[inline]
+  fn from_seed(seed: Self::Seed) -> Self {
+    let ext = seed.0.map(u32::from_le);
+    let state =
+      ext.iter().fold(0, |state, &x| lcg64_step(PCG_MUL_64, PCG_INC_64, state ^ x as u64));
+    Self::new(state, ext)
+  }
+
+  /// The `state` is used to seed the generator directly, and the extension
+  /// array is filled using a [PCG32] seeded with the `state`.
+  #[inline]
+  fn seed_from_u64(state: u64) -> Self {
+    let mut g = PCG32::seed(state, state);
+    Self::seed(state, core::array::from_fn(|_| g.next_u32()))
+  }
+}
+
+/// Lets any [RngCore] be used as a [Gen32].
+///
+/// ```
+/// use randomize::{Gen32, RngCoreGen, PCG32};
+/// # use rand_core::SeedableRng;
+/// // this could be any type that implements `RngCore`.
+/// let rng = PCG32::seed_from_u64(5);
+/// let mut g = RngCoreGen(rng);
+/// let roll = g.d6();
+/// assert!((1..=6).contains(&roll));
+/// ```
+#[derive(Debug, Clone, Default)]
+#[repr(transparent)]
+pub struct RngCoreGen<R>(pub R);
+impl<R: RngCore> Gen32 for RngCoreGen<R> {
+  #[inline]
+  fn next_u32(&mut self) -> u32 {
+    self.0.next_u32()
+  }
+}
+
+#[test]
+fn test_rand_core_matches_inherent() {
+  let mut a = PCG32::seed_from_u64(7);
+  let mut b = a.clone();
+  assert_eq!(RngCore::next_u32(&mut a), b.next_u32());
+  let lo = b.next_u32() as u64;
+  let hi = b.next_u32() as u64;
+  assert_eq!(RngCore::next_u64(&mut a), (hi << 32) | lo);
+  //
+  let mut seed = PCG32KSeed::<4>::default();
+  seed.as_mut().copy_from_slice(&[3; 16]);
+  let a = PCG32K::<4>::from_seed(seed);
+  assert_eq!(a.ext, [0x03030303; 4]);
+  let mut x = RngCoreGen(a.clone());
+  let mut y = a;
+  assert_eq!(x.next_u32(), y.next_u32());
+}
@@ -0,0 +1,200 @@
+use crate::{
+  formulas::{ieee754_random_f32, ieee754_random_f64},
+  Gen32,
+};
+
+/// Types that can be randomly generated.
+///
+/// Integers and `bool` are uniform over all of their values, floats are
+/// uniform over `0.0 ..= 1.0`, and `char` is uniform over all Unicode scalar
+/// values. Arrays and tuples generate each element in order, and an `Option`
+/// is `Some` half of the time.
+///
+/// `usize` and `isize` are always made from 64 random bits (and truncated on
+/// smaller targets), so they use the same number of draws on every target.
+///
+/// With the `derive` cargo feature you can also `#[derive(Random)]` for your
+/// own structs and enums. A struct generates each field in order, and an enum
+/// picks a variant uniformly and then generates that variant's fields.
+///
+/// ```
+/// # use randomize::{Gen32, PCG32};
+/// let mut g = PCG32::seed(1, 1);
+/// let (hp, pos): (u8, [i16; 2]) = g.random();
+/// let name_char: char = g.random();
+/// ```
+pub trait Random {
+  /// Generates a random value.
+  fn random<G: Gen32>(g: &mut G) -> Self;
+}
+
+impl Random for u32 {
+  #[inline]
+  fn random<G: Gen32>(g: &mut G) -> Self {
+    g.next_u32()
+  }
+}
+impl Random for u16 {
+  #[inline]
+  fn random<G: Gen32>(g: &mut G) -> Self {
+    (g.next_u32() >> 16) as u16
+  }
+}
+impl Random for u8 {
+  #[inline]
+  fn random<G: Gen32>(g: &mut G) -> Self {
+    (g.next_u32() >> 24) as u8
+  }
+}
+impl Random for u64 {
+  #[inline]
+  fn random<G: Gen32>(g: &mut G) -> Self {
+    let lo = u64::from(g.next_u32());
+    let hi = u64::from(g.next_u32());
+    (hi << 32) | lo
+  }
+}
+impl Random for u128 {
+  #[inline]
+  fn random<G: Gen32>(g: &mut G) -> Self {
+    let lo = u128::from(u64::random(g));
+    let hi = u128::from(u64::random(g));
+    (hi << 64) | lo
+  }
+}
+impl Random for usize {
+  #[inline]
+  fn random<G: Gen32>(g: &mut G) -> Self {
+    u64::random(g) as usize
+  }
+}
+
+macro_rules! impl_random_signed {
+  ($($i:ty => $u:ty),*) => {$(
+    impl Random for $i {
+      #[inline]
+      fn random<G: Gen32>(g: &mut G) -> Self {
+        <$u>::random(g) as $i
+      }
+    }
+  )*};
+}
+impl_random_signed!(i8 => u8, i16 => u16, i32 => u32, i64 => u64, i128 => u128, isize => usize);
+
+impl Random for bool {
+  #[inline]
+  fn random<G: Gen32>(g: &mut G) -> Self {
+    g.next_bool()
+  }
+}
+impl Random for f32 {
+  #[inline]
+  fn random<G: Gen32>(g: &mut G) -> Self {
+    ieee754_random_f32(|| g.next_u32(), false)
+  }
+}
+impl Random for f64 {
+  #[inline]
+  fn random<G: Gen32>(g: &mut G) -> Self {
+    ieee754_random_f64(|| g.next_u32(), false)
+  }
+}
+impl Random for char {
+  #[inline]
+  fn random<G: Gen32>(g: &mut G) -> Self {
+    g.next_char()
+  }
+}
+impl<T: Random> Random for Option<T> {
+  #[inline]
+  fn random<G: Gen32>(g: &mut G) -> Self {
+    if g.next_bool() {
+      Some(T::random(g))
+    } else {
+      None
+    }
+  }
+}
+impl<T: Random, const N: usize> Random for [T; N] {
+  #[inline]
+  fn random<G: Gen32>(g: &mut G) -> Self {
+    core::array::from_fn(|_| T::random(g))
+  }
+}
+
+macro_rules! impl_random_tuple {
+  ($($t:ident),*) => {
+    impl<$($t: Random),*> Random for ($($t,)*) {
+      #[inline]
+      #[allow(clippy::unused_unit)]
+      fn random<G: Gen32>(_g: &mut G) -> Self {
+        ($($t::random(_g),)*)
+      }
+    }
+  };
+}
+impl_random_tuple!();
+impl_random_tuple!(A);
+impl_random_tuple!(A, B);
+impl_random_tuple!(A, B, C);
+impl_random_tuple!(A, B, C, D);
+impl_random_tuple!(A, B, C, D, E);
+impl_random_tuple!(A, B, C, D, E, F);
+impl_random_tuple!(A, B, C, D, E, F, H);
+impl_random_tuple!(A, B, C, D, E, F, H, I);
+impl_random_tuple!(A, B, C, D, E, F, H, I, J);
+impl_random_tuple!(A, B, C, D, E, F, H, I, J, K);
+impl_random_tuple!(A, B, C, D, E, F, H, I, J, K, L);
+impl_random_tuple!(A, B, C, D, E, F, H, I, J, K, L, M);
+
+#[test]
+fn test_random_char_skips_surrogates() {
+  use crate::{testing::ScriptedGen, BoundedRandU32};
+  let scalars = BoundedRandU32::new(0x11_0000 - 0x800);
+  let script = [0, 0xD7FF, 0xD800, 0x11_0000 - 0x801].map(|x| scalars.raw_for(x));
+  let mut g = ScriptedGen::new(&script);
+  assert_eq!(char::random(&mut g), '\0');
+  assert_eq!(char::random(&mut g), '\u{D7FF}');
+  assert_eq!(char::random(&mut g), '\u{E000}');
+  assert_eq!(char::random(&mut g), char::MAX);
+}
+
+#[test]
+fn test_random_composites() {
+  use crate::testing::ScriptedGen;
+  let mut g = ScriptedGen::new(&[1, 2, 3, 4, u32::MAX, 0, 1, 7]);
+  let x: ([u32; 2], u64) = Random::random(&mut g);
+  assert_eq!(x, ([1, 2], (4 << 32) | 3));
+  let y: (Option<u8>, Option<u32>) = Random::random(&mut g);
+  assert_eq!(y, (Some(0), None));
+  assert_eq!(g.random::<i32>(), 7);
+
+  let mut g = crate::testing::CountingGen::new(crate::testing::ConstGen(1));
+  let _: (usize, isize) = g.random();
+  assert_eq!(g.count(), 4);
+}
+
+#[test]
+#[cfg(feature = "derive")]
+fn test_derive_random() {
+  use crate::{testing::ScriptedGen, BoundedRandU32};
+
+  #[derive(Debug, PartialEq, crate::Random)]
+  struct Point<T> {
+    x: T,
+    y: T,
+  }
+  #[derive(Debug, PartialEq, crate::Random)]
+  enum Shape {
+    Dot,
+    Circle(u32),
+    Rect { corner: Point<u32>, flipped: bool },
+  }
+
+  let variants = BoundedRandU32::new(3);
+  let script = [variants.raw_for(2), 5, 6, u32::MAX, variants.raw_for(0), variants.raw_for(1), 9];
+  let mut g = ScriptedGen::new(&script);
+  assert_eq!(g.random::<Shape>(), Shape::Rect { corner: Point { x: 5, y: 6 }, flipped: true });
+  assert_eq!(g.random::<Shape>(), Shape::Dot);
+  assert_eq!(g.random::<Shape>(), Shape::Circle(9));
+}
@@ -0,0 +1,257 @@
+//! Recording and replaying the raw output of a generator.
+
+use crate::{formulas::splitmix64, Error, Gen32};
+
+/// Storage for the values logged by a [Recorder].
+pub trait RecordBuf {
+  /// Adds a value to the end of the log.
+  fn push(&mut self, raw: u32);
+
+  /// The values logged so far, oldest first.
+  fn recorded(&self) -> &[u32];
+}
+
+/// A [RecordBuf] using a caller supplied slice.
+///
+/// ## Panics
+/// * Pushing more values than the slice can hold panics.
+#[derive(Debug)]
+pub struct SliceBuf<'b> {
+  buf: &'b mut [u32],
+  len: usize,
+}
+impl<'b> SliceBuf<'b> {
+  /// Makes an empty log that will be stored in the slice given.
+  #[inline]
+  #[must_use]
+  pub const fn new(buf: &'b mut [u32]) -> Self {
+    Self { buf, len: 0 }
+  }
+}
+impl RecordBuf for SliceBuf<'_> {
+  #[inline]
+  fn push(&mut self, raw: u32) {
+    assert!(self.len < self.buf.len(), "Recorder buffer is full");
+    self.buf[self.len] = raw;
+    self.len += 1;
+  }
+
+  #[inline]
+  fn recorded(&self) -> &[u32] {
+    &self.buf[..self.len]
+  }
+}
+
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+impl RecordBuf for alloc::vec::Vec<u32> {
+  #[inline]
+  fn push(&mut self, raw: u32) {
+    alloc::vec::Vec::push(self, raw);
+  }
+
+  #[inline]
+  fn recorded(&self) -> &[u32] {
+    self
+  }
+}
+
+/// Wraps a generator and logs every `u32` drawn from it.
+///
+/// The log can later be given to a [Replayer] to repeat the exact same
+/// sequence of values without knowing how the generator was seeded.
+///
+/// ```
+/// # use randomize::{Gen32, PCG32, Recorder, Replayer};
+/// let mut buf = [0_u32; 64];
+/// let mut rec = Recorder::with_slice(PCG32::seed(1, 1), &mut buf);
+/// let rolls = [rec.d6(), rec.d6(), rec.d20()];
+///
+/// let mut rep = Replayer::new(rec.recorded());
+/// assert_eq!(rolls, [rep.d6(), rep.d6(), rep.d20()]);
+/// assert!(rep.finish().is_ok());
+/// ```
+#[derive(Debug, Clone)]
+pub struct Recorder<G, B> {
+  gen: G,
+  buf: B,
+}
+impl<G: Gen32, B: RecordBuf> Recorder<G, B> {
+  /// Wraps a generator, logging into the buffer given.
+  #[inline]
+  #[must_use]
+  pub const fn new(gen: G, buf: B) -> Self {
+    Self { gen, buf }
+  }
+
+  /// The values drawn so far, oldest first.
+  #[inline]
+  #[must_use]
+  pub fn recorded(&self) -> &[u32] {
+    self.buf.recorded()
+  }
+
+  /// Unwraps the generator and the log.
+  #[inline]
+  #[must_use]
+  pub fn into_parts(self) -> (G, B) {
+    (self.gen, self.buf)
+  }
+}
+impl<'b, G: Gen32> Recorder<G, SliceBuf<'b>> {
+  /// Wraps a generator, logging into the slice given.
+  ///
+  /// ## Panics
+  /// * Drawing more values than the slice can hold panics.
+  #[inline]
+  #[must_use]
+  pub const fn with_slice(gen: G, buf: &'b mut [u32]) -> Self {
+    Self::new(gen, SliceBuf::new(buf))
+  }
+}
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+impl<G: Gen32> Recorder<G, alloc::vec::Vec<u32>> {
+  /// Wraps a generator, logging into a new `Vec`.
+  #[inline]
+  #[must_use]
+  pub const fn with_vec(gen: G) -> Self {
+    Self::new(gen, alloc::vec::Vec::new())
+  }
+}
+impl<G: Gen32, B: RecordBuf> Gen32 for Recorder<G, B> {
+  #[inline]
+  fn next_u32(&mut self) -> u32 {
+    let raw = self.gen.next_u32();
+    self.buf.push(raw);
+    raw
+  }
+}
+
+/// Plays back a log made by a [Recorder].
+///
+/// If the code using the replayer draws a different number of values than
+/// were logged, the replay has diverged, and [finish](Self::finish) reports
+/// it. Past the end of the log, `next_u32` keeps giving filler values (which
+/// vary, so that a bounded sampler can't reject them forever) and the code
+/// runs to completion. Use [try_next_u32](Self::try_next_u32) to stop at the
+/// end of the log instead.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Replayer<'a> {
+  log: &'a [u32],
+  drawn: usize,
+}
+impl<'a> Replayer<'a> {
+  /// Makes a replayer that starts at the beginning of the log.
+  #[inline]
+  #[must_use]
+  pub const fn new(log: &'a [u32]) -> Self {
+    Self { log, drawn: 0 }
+  }
+
+  /// The number of values drawn so far.
+  #[inline]
+  #[must_use]
+  pub const fn drawn(&self) -> usize {
+    self.drawn
+  }
+
+  /// Checks that exactly the logged values were drawn.
+  ///
+  /// ## Failure
+  /// * If fewer or more values were drawn than were logged, this gives
+  ///   [Error::ReplayDiverged].
+  #[inline]
+  pub const fn finish(self) -> Result<(), Error> {
+    if self.drawn == self.log.len() {
+      Ok(())
+    } else {
+      Err(Error::ReplayDiverged { recorded: self.log.len(), drawn: self.drawn })
+    }
+  }
+
+  /// Gives the next logged value.
+  ///
+  /// ## Failure
+  /// * If the log is used up, this gives [Error::ReplayDiverged] with a
+  ///   `drawn` count that includes this draw.
+  #[inline]
+  pub const fn try_next_u32(&mut self) -> Result<u32, Error> {
+    if self.drawn < self.log.len() {
+      let out = self.log[self.drawn];
+      self.drawn += 1;
+      Ok(out)
+    } else {
+      Err(Error::ReplayDiverged { recorded: self.log.len(), drawn: self.drawn + 1 })
+    }
+  }
+
+  /// Gives the next logged value.
+  ///
+  /// If the log is used up this gives a filler value, and [finish](Self::finish)
+  /// will report the divergence.
+  #[inline]
+  pub const fn next_u32(&mut self) -> u32 {
+    let out = if self.drawn < self.log.len() {
+      self.log[self.drawn]
+    } else {
+      splitmix64(0, self.drawn as u64) as u32
+    };
+    self.drawn += 1;
+    out
+  }
+}
+impl Gen32 for Replayer<'_> {
+  #[inline]
+  fn next_u32(&mut self) -> u32 {
+    Replayer::next_u32(self)
+  }
+}
+
+#[test]
+fn test_record_and_replay() {
+  use crate::PCG32;
+
+  let mut buf = [0_u32; 8];
+  let mut rec = Recorder::with_slice(PCG32::seed(5, 6), &mut buf);
+  let a = [rec.next_u32(), rec.next_u32(), rec.next_u32()];
+  let (mut gen, log) = rec.into_parts();
+  assert_eq!(log.recorded(), &a);
+
+  let mut rep = Replayer::new(log.recorded());
+  assert_eq!([rep.next_u32(), rep.next_u32(), rep.next_u32()], a);
+  assert_eq!(rep.try_next_u32(), Err(Error::ReplayDiverged { recorded: 3, drawn: 4 }));
+  assert_eq!(rep.drawn(), 3);
+  assert!(rep.finish().is_ok());
+
+  let rep = Replayer::new(log.recorded());
+  assert_eq!(rep.finish(), Err(Error::ReplayDiverged { recorded: 3, drawn: 0 }));
+
+  // the recorder doesn't change what the generator gives.
+  let mut fresh = PCG32::seed(5, 6);
+  fresh.jump(3);
+  assert_eq!(gen.next_u32(), fresh.next_u32());
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn test_record_vec() {
+  use crate::PCG32;
+
+  let mut rec = Recorder::with_vec(PCG32::seed(1, 1));
+  let rolls: [i32; 10] = core::array::from_fn(|_| rec.d20());
+  let (_, log) = rec.into_parts();
+  let mut rep = Replayer::new(&log);
+  assert_eq!(rolls, core::array::from_fn(|_| rep.d20()));
+  assert!(rep.finish().is_ok());
+}
+
+#[test]
+fn test_replay_overrun() {
+  // the second roll runs past the log, which must not hang, and then finish
+  // reports the divergence.
+  let mut rep = Replayer::new(&[u32::MAX]);
+  rep.d6();
+  rep.d6();
+  assert!(matches!(rep.finish(), Err(Error::ReplayDiverged { recorded: 1, drawn: 2.. })));
+}
@@ -0,0 +1,292 @@
+//! Hierarchical seed derivation.
+
+use crate::{PCG32, PCG32K, PCG64DXSM};
+
+const POOL_SIZE: usize = 4;
+const MAX_ENTROPY_WORDS: usize = 4;
+const XSHIFT: u32 = 16;
+const INIT_A: u32 = 0x43b0d7e5;
+const MULT_A: u32 = 0x931e8875;
+const INIT_B: u32 = 0x8b51f9dd;
+const MULT_B: u32 = 0x58f38ded;
+const MIX_MULT_L: u32 = 0xca01f9dd;
+const MIX_MULT_R: u32 = 0x4973f715;
+
+/// A seed that can be split into any number of independent child seeds.
+///
+/// This is modeled on NumPy's `SeedSequence`, and uses the same hashing
+/// algorithm. A `SeedSeq` is made from some entropy (such as a master seed for
+/// a whole program run) and a "spawn key" (the path of child indexes taken to
+/// get to this seed). All of it is hashed together into a small pool, and then
+/// the pool is used to make the state for new generators.
+///
+/// Two seeds with the same entropy but different spawn keys will make
+/// generators that have no detectable relation to each other, so this is a
+/// good way to give each worker of a big job its own generator.
+///
+/// ```
+/// use randomize::SeedSeq;
+/// let root = SeedSeq::new(12345);
+/// // worker 7 of job 3
+/// let mut g = root.spawn(3).spawn(7).pcg32();
+/// // it's reproducible
+/// assert_eq!(g.next_u32(), root.spawn(3).spawn(7).pcg32().next_u32());
+/// ```
+///
+/// * The spawn key can be at most [MAX_SPAWN_DEPTH](Self::MAX_SPAWN_DEPTH)
+///   elements long.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct SeedSeq {
+  entropy: [u32; MAX_ENTROPY_WORDS],
+  entropy_len: usize,
+  spawn_key: [u32; SeedSeq::MAX_SPAWN_DEPTH],
+  spawn_len: usize,
+  pool: [u32; POOL_SIZE],
+}
+impl SeedSeq {
+  /// The maximum number of nested [spawn](Self::spawn) calls.
+  pub const MAX_SPAWN_DEPTH: usize = 16;
+
+  /// Makes a new root seed from the entropy value given.
+  #[inline]
+  #[must_use]
+  pub const fn new(entropy: u128) -> Self {
+    // Note: The entropy is split into the minimum number of `u32`
+    // values needed to hold it (with at least one value).
+    let mut words = [0_u32; MAX_ENTROPY_WORDS];
+    let mut len = 0;
+    let mut e = entropy;
+    while e > 0 || len == 0 {
+      words[len] = e as u32;
+      e >>= 32;
+      len += 1;
+    }
+    Self::with_spawn_key(words, len, [0; Self::MAX_SPAWN_DEPTH], 0)
+  }
+
+  /// Makes a new root seed using entropy from
+  /// [getrandom](getrandom::getrandom).
+  #[cfg(feature = "getrandom")]
+  #[cfg_attr(docsrs, doc(cfg(feature = "getrandom")))]
+  #[inline]
+  pub fn from_getrandom() -> Result<Self, crate::Error> {
+    use bytemuck::bytes_of_mut;
+
+    let mut entropy = 0_u128;
+    getrandom::getrandom(bytes_of_mut(&mut entropy))?;
+
+    Ok(Self::new(entropy))
+  }
+
+  #[inline]
+  #[must_use]
+  const fn with_spawn_key(
+    entropy: [u32; MAX_ENTROPY_WORDS], entropy_len: usize, spawn_key: [u32; Self::MAX_SPAWN_DEPTH],
+    spawn_len: usize,
+  ) -> Self {
+    let mut out = Self { entropy, entropy_len, spawn_key, spawn_len, pool: [0; POOL_SIZE] };
+    out.mix_entropy();
+    out
+  }
+
+  /// Gets the child seed with the given index.
+  ///
+  /// The same parent and index always give the same child.
+  ///
+  /// ## Panics
+  /// * If this seed is already at [MAX_SPAWN_DEPTH](Self::MAX_SPAWN_DEPTH).
+  #[inline]
+  #[must_use]
+  pub const fn spawn(&self, index: u32) -> Self {
+    assert!(self.spawn_len < Self::MAX_SPAWN_DEPTH, "SeedSeq spawn depth exceeded");
+    let mut spawn_key = self.spawn_key;
+    spawn_key[self.spawn_len] = index;
+    Self::with_spawn_key(self.entropy, self.entropy_len, spawn_key, self.spawn_len + 1)
+  }
+
+  /// The path of child indexes used to get to this seed from the root seed.
+  #[inline]
+  #[must_use]
+  pub fn spawn_key(&self) -> &[u32] {
+    &self.spawn_key[..self.spawn_len]
+  }
+
+  /// Fills the buffer with seed words.
+  ///
+  /// Each position of the output depends on the entire entropy pool, and the
+  /// output for a given seed is always the same, so asking for more words only
+  /// adds values to the end of the output.
+  #[inline]
+  pub const fn generate_state(&self, out: &mut [u32]) {
+    let mut hash_const = INIT_B;
+    let mut i = 0;
+    while i < out.len() {
+      let mut data = self.pool[i % POOL_SIZE];
+      data ^= hash_const;
+      hash_const = hash_const.wrapping_mul(MULT_B);
+      data = data.wrapping_mul(hash_const);
+      data ^= data >> XSHIFT;
+      out[i] = data;
+      i += 1;
+    }
+  }
+
+  /// Makes a [PCG32] from this seed.
+  #[inline]
+  #[must_use]
+  pub const fn pcg32(&self) -> PCG32 {
+    let mut w = [0_u32; 4];
+    self.generate_state(&mut w);
+    let state = (w[0] as u64) | ((w[1] as u64) << 32);
+    let inc = (w[2] as u64) | ((w[3] as u64) << 32);
+    PCG32::new(state, inc | 1)
+  }
+
+  /// Makes a [PCG32K] from this seed.
+  #[inline]
+  #[must_use]
+  pub const fn pcg32k<const K: usize>(&self) -> PCG32K<K> {
+    let mut w = [0_u32; 2];
+    self.generate_state(&mut w);
+    let state = (w[0] as u64) | ((w[1] as u64) << 32);
+    // Note: We want the same words that a `2 + K` word buffer would
+    // get, so we have to step the hash constant past the state words first.
+    let mut ext = [0_u32; K];
+    let mut hash_const = INIT_B.wrapping_mul(MULT_B).wrapping_mul(MULT_B);
+    let mut i = 0;
+    while i < K {
+      let mut data = self.pool[(i + 2) % POOL_SIZE];
+      data ^= hash_const;
+      hash_const = hash_const.wrapping_mul(MULT_B);
+      data = data.wrapping_mul(hash_const);
+      data ^= data >> XSHIFT;
+      ext[i] = data;
+      i += 1;
+    }
+    PCG32K::new(state, ext)
+  }
+
+  /// Makes a [PCG64DXSM] from this seed.
+  ///
+  /// Four `u64` words of state are generated. The first two are the `seed` and
+  /// the last two are the `inc` passed to [PCG64DXSM::seed].
+  #[inline]
+  #[must_use]
+  pub const fn pcg64dxsm(&self) -> PCG64DXSM {
+    let mut w = [0_u32; 8];
+    self.generate_state(&mut w);
+    let mut v = [0_u64; 4];
+    let mut i = 0;
+    while i < 4 {
+      v[i] = (w[2 * i] as u64) | ((w[2 * i + 1] as u64) << 32);
+      i += 1;
+    }
+    let seed = ((v[0] as u128) << 64) | (v[1] as u128);
+    let inc = ((v[2] as u128) << 64) | (v[3] as u128);
+    PCG64DXSM::seed(seed, inc)
+  }
+
+  /// Hashes the entropy and the spawn key into the pool.
+  #[inline]
+  const fn mix_entropy(&mut self) {
+    // Note: When there's a spawn key, the entropy is padded out to
+    // the pool size so that the spawn key words can't be confused with
+    // entropy words.
+    let run_len =
+      if self.spawn_len > 0 && self.entropy_len < POOL_SIZE { POOL_SIZE } else { self.entropy_len };
+    let total_len = run_len + self.spawn_len;
+    let mut hash_const = INIT_A;
+    let mut i = 0;
+    while i < POOL_SIZE {
+      let value = if i < total_len { self.entropy_word(run_len, i) } else { 0 };
+      self.pool[i] = hashmix(value, &mut hash_const);
+      i += 1;
+    }
+    let mut i_src = 0;
+    while i_src < POOL_SIZE {
+      let mut i_dst = 0;
+      while i_dst < POOL_SIZE {
+        if i_src != i_dst {
+          let h = hashmix(self.pool[i_src], &mut hash_const);
+          self.pool[i_dst] = mix(self.pool[i_dst], h);
+        }
+        i_dst += 1;
+      }
+      i_src += 1;
+    }
+    let mut i_src = POOL_SIZE;
+    while i_src < total_len {
+      let mut i_dst = 0;
+      while i_dst < POOL_SIZE {
+        let h = hashmix(self.entropy_word(run_len, i_src), &mut hash_const);
+        self.pool[i_dst] = mix(self.pool[i_dst], h);
+        i_dst += 1;
+      }
+      i_src += 1;
+    }
+  }
+
+  /// Gets a word of the (padded) entropy followed by the spawn key.
+  #[inline]
+  #[must_use]
+  const fn entropy_word(&self, run_len: usize, i: usize) -> u32 {
+    if i < self.entropy_len {
+      self.entropy[i]
+    } else if i < run_len {
+      0
+    } else {
+      self.spawn_key[i - run_len]
+    }
+  }
+}
+
+#[inline]
+#[must_use]
+const fn hashmix(mut value: u32, hash_const: &mut u32) -> u32 {
+  value ^= *hash_const;
+  *hash_const = hash_const.wrapping_mul(MULT_A);
+  value = value.wrapping_mul(*hash_const);
+  value ^= value >> XSHIFT;
+  value
+}
+
+#[inline]
+#[must_use]
+const fn mix(x: u32, y: u32) -> u32 {
+  let result = MIX_MULT_L.wrapping_mul(x).wrapping_sub(MIX_MULT_R.wrapping_mul(y));
+  result ^ (result >> XSHIFT)
+}
+
+#[test]
+fn test_seed_seq_spawn() {
+  let root = SeedSeq::new(12345);
+  let a = root.spawn(3).spawn(7);
+  assert_eq!(a.spawn_key(), &[3, 7]);
+  assert_eq!(a, SeedSeq::new(12345).spawn(3).spawn(7));
+  // Different paths, and different entropy, give different pools.
+  let others =
+    [root.clone(), root.spawn(3), root.spawn(7).spawn(3), SeedSeq::new(12346).spawn(3).spawn(7)];
+  for other in others {
+    assert_ne!(a.pool, other.pool);
+  }
+  // A padded entropy value isn't confused with a spawn key.
+  assert_ne!(SeedSeq::new(1).spawn(0).pool, SeedSeq::new(1 | (1 << 64)).pool);
+}
+
+#[test]
+fn test_seed_seq_generators() {
+  let s = SeedSeq::new(99).spawn(1);
+  let mut words = [0_u32; 6];
+  s.generate_state(&mut words);
+  let x = s.pcg32();
+  assert_eq!(x.state, (words[0] as u64) | ((words[1] as u64) << 32));
+  assert_eq!(x.inc, ((words[2] as u64) | ((words[3] as u64) << 32)) | 1);
+  let y = s.pcg32k::<4>();
+  assert_eq!(y.state, x.state);
+  assert_eq!(y.ext, [words[2], words[3], words[4], words[5]]);
+  // Siblings get unrelated streams.
+  let mut a = SeedSeq::new(99).spawn(0).pcg32();
+  let mut b = SeedSeq::new(99).spawn(1).pcg32();
+  assert_ne!(a.inc, b.inc);
+  assert_ne!(a.next_u32(), b.next_u32());
+}
@@ -0,0 +1,165 @@
+use crate::{BoundedRandU16, BoundedRandU32, PCG32K};
+use core::{fmt, marker::PhantomData};
+use serde::{
+  de::{self, MapAccess, SeqAccess, Visitor},
+  ser::SerializeStruct,
+  Deserialize, Deserializer, Serialize, Serializer,
+};
+
+impl<const K: usize> Serialize for PCG32K<K> {
+  #[inline]
+  fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+    let mut s = serializer.serialize_struct("PCG32K", 2)?;
+    s.serialize_field("state", &self.state)?;
+    s.serialize_field("ext", &self.ext[..])?;
+    s.end()
+  }
+}
+
+impl<'de, const K: usize> Deserialize<'de> for PCG32K<K> {
+  #[inline]
+  fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+    #[derive(Deserialize)]
+    #[serde(field_identifier, rename_all = "lowercase")]
+    enum Field {
+      State,
+      Ext,
+    }
+
+    struct PCG32KVisitor<const K: usize>;
+    impl<'de, const K: usize> Visitor<'de> for PCG32KVisitor<K> {
+      type Value = PCG32K<K>;
+
+      fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "a PCG32K<{K}> with a state and {K} ext elements")
+      }
+
+      fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+        let state = seq.next_element()?.ok_or_else(|| de::Error::invalid_length(0, &self))?;
+        let ExtArray(ext) =
+          seq.next_element()?.ok_or_else(|| de::Error::invalid_length(1, &self))?;
+        Ok(PCG32K::new(state, ext))
+      }
+
+      fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+        let mut state = None;
+        let mut ext = None;
+        while let Some(key) = map.next_key()? {
+          match key {
+            Field::State => {
+              if state.is_some() {
+                return Err(de::Error::duplicate_field("state"));
+              }
+              state = Some(map.next_value()?);
+            }
+            Field::Ext => {
+              if ext.is_some() {
+                return Err(de::Error::duplicate_field("ext"));
+              }
+              let ExtArray(array) = map.next_value()?;
+              ext = Some(array);
+            }
+          }
+        }
+        let state = state.ok_or_else(|| de::Error::missing_field("state"))?;
+        let ext = ext.ok_or_else(|| de::Error::missing_field("ext"))?;
+        Ok(PCG32K::new(state, ext))
+      }
+    }
+
+    deserializer.deserialize_struct("PCG32K", &["state", "ext"], PCG32KVisitor::<K>)
+  }
+}
+
+/// Deserializes a `[u32; K]` from a sequence of exactly `K` elements.
+struct ExtArray<const K: usize>([u32; K]);
+impl<'de, const K: usize> Deserialize<'de> for ExtArray<K> {
+  #[inline]
+  fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+    struct ExtVisitor<const K: usize>(PhantomData<[u32; K]>);
+    impl<'de, const K: usize> Visitor<'de> for ExtVisitor<K> {
+      type Value = ExtArray<K>;
+
+      fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "a sequence of {K} u32 values")
+      }
+
+      fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+        let mut ext = [0_u32; K];
+        for (i, x) in ext.iter_mut().enumerate() {
+          *x = seq.next_element()?.ok_or_else(|| de::Error::invalid_length(i, &self))?;
+        }
+        if seq.next_element::<de::IgnoredAny>()?.is_some() {
+          return Err(de::Error::invalid_length(K + 1, &self));
+        }
+        Ok(ExtArray(ext))
+      }
+    }
+
+    deserializer.deserialize_seq(ExtVisitor::<K>(PhantomData))
+  }
+}
+
+// Note: The bounded types are stored as just their `count`, and the
+// threshold is recomputed on load. This way a bad file can't make an invalid
+// value.
+
+impl Serialize for BoundedRandU32 {
+  #[inline]
+  fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.serialize_newtype_struct("BoundedRandU32", &self.count())
+  }
+}
+
+impl<'de> Deserialize<'de> for BoundedRandU32 {
+  #[inline]
+  fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+    #[derive(Deserialize)]
+    #[serde(rename = "BoundedRandU32")]
+    struct Count(u32);
+    let Count(count) = Count::deserialize(deserializer)?;
+    Self::try_new(count).map_err(de::Error::custom)
+  }
+}
+
+impl Serialize for BoundedRandU16 {
+  #[inline]
+  fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.serialize_newtype_struct("BoundedRandU16", &self.count())
+  }
+}
+
+impl<'de> Deserialize<'de> for BoundedRandU16 {
+  #[inline]
+  fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+    #[derive(Deserialize)]
+    #[serde(rename = "BoundedRandU16")]
+    struct Count(u16);
+    let Count(count) = Count::deserialize(deserializer)?;
+    Self::try_new(count).map_err(de::Error::custom)
+  }
+}
+
+#[test]
+fn test_serde_round_trip() {
+  use crate::PCG32;
+
+  let mut x = PCG32::seed(1, 2);
+  let json = serde_json::to_value(&x).unwrap();
+  assert_eq!(json, serde_json::json!({"state": x.state, "inc": x.inc}));
+  let mut y: PCG32 = serde_json::from_value(json).unwrap();
+  assert_eq!(x.next_u32(), y.next_u32());
+  //
+  let mut x = PCG32K::<3>::seed(1, [4, 5, 6]);
+  let json = serde_json::to_string(&x).unwrap();
+  let mut y: PCG32K<3> = serde_json::from_str(&json).unwrap();
+  assert_eq!(x.next_u32(), y.next_u32());
+  assert!(serde_json::from_str::<PCG32K<2>>(&json).is_err());
+  assert!(serde_json::from_str::<PCG32K<4>>(&json).is_err());
+  //
+  let json = serde_json::to_string(&BoundedRandU32::_6).unwrap();
+  assert_eq!(json, "6");
+  assert_eq!(serde_json::from_str::<BoundedRandU32>(&json).unwrap(), BoundedRandU32::_6);
+  assert_eq!(serde_json::from_str::<BoundedRandU16>("20").unwrap(), BoundedRandU16::_20);
+  assert!(serde_json::from_str::<BoundedRandU16>("0").is_err());
+}
@@ -0,0 +1,415 @@
+//! A compact binary encoding of generator state.
+//!
+//! Every encoding starts with an 8 byte header:
+//!
+//! * bytes 0 and 1: the magic value `b"RZ"`
+//! * byte 2: the format version (currently 1)
+//! * byte 3: the generator type tag
+//! * bytes 4 to 7: a type parameter as a little-endian `u32` (this is `K` for
+//!   a [PCG32K], and 0 for all other types)
+//!
+//! After the header is the generator's state, with all values stored in
+//! little-endian order.
+//!
+//! | Type | Tag | State |
+//! |:-|:-:|:-|
+//! | [PCG32] | 1 | `state: u64`, `inc: u64` |
+//! | [PCG32K] | 2 | `state: u64`, `ext: [u32; K]` |
+//! | [PCG64DXSM] | 3 | `state: u128`, `inc: u128` |
+//! | [MCG32] | 4 | `state: u64` |
+//! | [MCG64] | 5 | `state: u128` |
+//! | [Philox4x32] | 6 | `key: [u32; 2]`, `counter: u128`, `index: u32` |
+//!
+//! The Philox `counter` and `index` are the generator's
+//! [block_position](Philox4x32::block_position).
+//!
+//! Decoding checks the magic value, the version, the type tag, the type
+//! parameter, and the total length, so bytes written by one type of generator
+//! (or one `K` of [PCG32K]) can't be read back as another.
+
+use crate::{Error, Philox4x32, MCG32, MCG64, PCG32, PCG32K, PCG64DXSM};
+use core::fmt;
+
+const MAGIC: [u8; 2] = *b"RZ";
+const VERSION: u8 = 1;
+const HEADER_LEN: usize = 8;
+
+const TAG_PCG32: u8 = 1;
+const TAG_PCG32K: u8 = 2;
+const TAG_PCG64DXSM: u8 = 3;
+const TAG_MCG32: u8 = 4;
+const TAG_MCG64: u8 = 5;
+const TAG_PHILOX4X32: u8 = 6;
+
+/// An error from encoding or decoding generator state bytes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum StateError {
+  /// The data doesn't start with the expected magic bytes.
+  BadMagic,
+  /// The data is from a format version that this crate doesn't know.
+  UnknownVersion(u8),
+  /// The data is for a different generator type.
+  WrongType {
+    /// The type tag of the type being decoded.
+    expected: u8,
+    /// The type tag found in the data.
+    found: u8,
+  },
+  /// The data is for a [PCG32K] with a different `K` value.
+  WrongK {
+    /// The `K` of the type being decoded.
+    expected: u32,
+    /// The `K` found in the data.
+    found: u32,
+  },
+  /// The data is shorter than the generator's encoding.
+  Truncated {
+    /// The number of bytes needed.
+    needed: usize,
+    /// The number of bytes given.
+    found: usize,
+  },
+  /// The data is longer than the generator's encoding.
+  TrailingBytes {
+    /// The number of bytes needed.
+    needed: usize,
+    /// The number of bytes given.
+    found: usize,
+  },
+  /// The output buffer is too small to hold the encoding.
+  BufferTooSmall {
+    /// The number of bytes needed.
+    needed: usize,
+    /// The size of the buffer given.
+    found: usize,
+  },
+}
+impl fmt::Display for StateError {
+  #[inline]
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      Self::BadMagic => write!(f, "not generator state data"),
+      Self::UnknownVersion(v) => write!(f, "unknown state format version {v}"),
+      Self::WrongType { expected, found } => {
+        write!(f, "wrong generator type: expected tag {expected}, found tag {found}")
+      }
+      Self::WrongK { expected, found } => {
+        write!(f, "wrong extension size: expected K={expected}, found K={found}")
+      }
+      Self::Truncated { needed, found } => {
+        write!(f, "state data truncated: needed {needed} bytes, found {found}")
+      }
+      Self::TrailingBytes { needed, found } => {
+        write!(f, "state data too long: needed {needed} bytes, found {found}")
+      }
+      Self::BufferTooSmall { needed, found } => {
+        write!(f, "output buffer too small: needed {needed} bytes, found {found}")
+      }
+    }
+  }
+}
+
+impl core::error::Error for StateError {}
+
+/// Writes little-endian values into an output buffer.
+struct Writer<'a> {
+  out: &'a mut [u8],
+  pos: usize,
+}
+impl<'a> Writer<'a> {
+  /// Checks the buffer size and writes the header.
+  #[inline]
+  fn new(out: &'a mut [u8], tag: u8, param: u32, len: usize) -> Result<Self, StateError> {
+    if out.len() < len {
+      return Err(StateError::BufferTooSmall { needed: len, found: out.len() });
+    }
+    let mut w = Self { out, pos: 0 };
+    w.put(&MAGIC);
+    w.put(&[VERSION, tag]);
+    w.put(&param.to_le_bytes());
+    Ok(w)
+  }
+  #[inline]
+  fn put(&mut self, bytes: &[u8]) {
+    self.out[self.pos..self.pos + bytes.len()].copy_from_slice(bytes);
+    self.pos += bytes.len();
+  }
+  #[inline]
+  const fn finish(self) -> usize {
+    self.pos
+  }
+}
+
+/// Reads little-endian values out of an input buffer.
+struct Reader<'a> {
+  bytes: &'a [u8],
+  pos: usize,
+}
+impl<'a> Reader<'a> {
+  /// Checks the header and the total length.
+  #[inline]
+  fn new(bytes: &'a [u8], tag: u8, param: u32, len: usize) -> Result<Self, StateError> {
+    if bytes.len() < HEADER_LEN {
+      return Err(StateError::Truncated { needed: len, found: bytes.len() });
+    }
+    if bytes[0..2] != MAGIC {
+      return Err(StateError::BadMagic);
+    }
+    if bytes[2] != VERSION {
+      return Err(StateError::UnknownVersion(bytes[2]));
+    }
+    if bytes[3] != tag {
+      return Err(StateError::WrongType { expected: tag, found: bytes[3] });
+    }
+    let found_param = u32::from_le_bytes([bytes[4], bytes[5], bytes[6], bytes[7]]);
+    if found_param != param {
+      return Err(StateError::WrongK { expected: param, found: found_param });
+    }
+    if bytes.len() < len {
+      return Err(StateError::Truncated { needed: len, found: bytes.len() });
+    }
+    if bytes.len() > len {
+      return Err(StateError::TrailingBytes { needed: len, found: bytes.len() });
+    }
+    Ok(Self { bytes, pos: HEADER_LEN })
+  }
+  #[inline]
+  fn take<const N: usize>(&mut self) -> [u8; N] {
+    let mut a = [0; N];
+    a.copy_from_slice(&self.bytes[self.pos..self.pos + N]);
+    self.pos += N;
+    a
+  }
+  #[inline]
+  fn u32(&mut self) -> u32 {
+    u32::from_le_bytes(self.take())
+  }
+  #[inline]
+  fn u64(&mut self) -> u64 {
+    u64::from_le_bytes(self.take())
+  }
+  #[inline]
+  fn u128(&mut self) -> u128 {
+    u128::from_le_bytes(self.take())
+  }
+}
+
+/// Implements the byte encoding for a generator type without parameters.
+///
+/// The `write` block puts the state into the [Writer], and the `read`
+/// expression builds the generator from the [Reader].
+macro_rules! impl_state_bytes {
+  ($t:ty, $tag:expr, $len:expr, |$g:ident, $w:ident| $write:block, |$r:ident| $read:expr) => {
+    impl $t {
+      /// The number of bytes used by [to_bytes](Self::to_bytes).
+      pub const STATE_BYTES: usize = HEADER_LEN + $len;
+
+      /// Writes the generator's state into the buffer given, using the
+      /// [state bytes encoding](crate::state_bytes).
+      ///
+      /// **Returns:** the number of bytes written.
+      ///
+      /// ## Failure
+      /// * If the buffer is smaller than [STATE_BYTES](Self::STATE_BYTES).
+      #[inline]
+      pub fn to_bytes(&self, out: &mut [u8]) -> Result<usize, Error> {
+        let $g = self;
+        let mut $w = Writer::new(out, $tag, 0, Self::STATE_BYTES)?;
+        $write
+        Ok($w.finish())
+      }
+
+      /// Reads a generator from bytes written by [to_bytes](Self::to_bytes).
+      ///
+      /// ## Failure
+      /// * If the data isn't an [encoding](crate::state_bytes) of this type of
+      ///   generator.
+      #[inline]
+      pub fn from_bytes(bytes: &[u8]) -> Result<Self, Error> {
+        let mut $r = Reader::new(bytes, $tag, 0, Self::STATE_BYTES)?;
+        Ok($read)
+      }
+    }
+  };
+}
+
+impl_state_bytes!(
+  PCG32,
+  TAG_PCG32,
+  16,
+  |g, w| {
+    w.put(&g.state.to_le_bytes());
+    w.put(&g.inc.to_le_bytes());
+  },
+  |r| Self::new(r.u64(), r.u64())
+);
+
+impl<const K: usize> PCG32K<K> {
+  /// The number of bytes used by [to_bytes](Self::to_bytes).
+  pub const STATE_BYTES: usize = HEADER_LEN + 8 + 4 * K;
+
+  /// Writes the generator's state into the buffer given, using the
+  /// [state bytes encoding](crate::state_bytes).
+  ///
+  /// **Returns:** the number of bytes written.
+  ///
+  /// ## Failure
+  /// * If the buffer is smaller than [STATE_BYTES](Self::STATE_BYTES).
+  /// * If `K` is larger than `u32::MAX`.
+  #[inline]
+  pub fn to_bytes(&self, out: &mut [u8]) -> Result<usize, Error> {
+    let mut w = Writer::new(out, TAG_PCG32K, Self::k_param()?, Self::STATE_BYTES)?;
+    w.put(&self.state.to_le_bytes());
+    for x in &self.ext {
+      w.put(&x.to_le_bytes());
+    }
+    Ok(w.finish())
+  }
+
+  /// Reads a generator from bytes written by [to_bytes](Self::to_bytes).
+  ///
+  /// ## Failure
+  /// * If the data isn't an [encoding](crate::state_bytes) of this type of
+  ///   generator with this `K`.
+  /// * If `K` is larger than `u32::MAX`.
+  #[inline]
+  pub fn from_bytes(bytes: &[u8]) -> Result<Self, Error> {
+    let mut r = Reader::new(bytes, TAG_PCG32K, Self::k_param()?, Self::STATE_BYTES)?;
+    let state = r.u64();
+    let ext = core::array::from_fn(|_| r.u32());
+    Ok(Self::new(state, ext))
+  }
+
+  #[inline]
+  fn k_param() -> Result<u32, Error> {
+    u32::try_from(K).map_err(|_| Error::InvalidParameter("K is too large to encode"))
+  }
+}
+
+impl_state_bytes!(
+  PCG64DXSM,
+  TAG_PCG64DXSM,
+  32,
+  |g, w| {
+    w.put(&g.state.to_le_bytes());
+    w.put(&g.inc.to_le_bytes());
+  },
+  |r| Self::new(r.u128(), r.u128())
+);
+
+impl_state_bytes!(
+  MCG32,
+  TAG_MCG32,
+  8,
+  |g, w| {
+    w.put(&g.state.to_le_bytes());
+  },
+  |r| Self::new(r.u64())
+);
+
+impl_state_bytes!(
+  MCG64,
+  TAG_MCG64,
+  16,
+  |g, w| {
+    w.put(&g.state.to_le_bytes());
+  },
+  |r| Self::new(r.u128())
+);
+
+impl_state_bytes!(
+  Philox4x32,
+  TAG_PHILOX4X32,
+  28,
+  |g, w| {
+    w.put(&g.key[0].to_le_bytes());
+    w.put(&g.key[1].to_le_bytes());
+    let (counter, index) = g.block_position();
+    w.put(&counter.to_le_bytes());
+    w.put(&(index as u32).to_le_bytes());
+  },
+  |r| {
+    let key = [r.u32(), r.u32()];
+    let mut out = Self::new(key);
+    let counter = r.u128();
+    out.set_block_position(counter, r.u32() as usize);
+    out
+  }
+);
+
+#[test]
+fn test_state_bytes_round_trip() {
+  let mut buf = [0_u8; 64];
+  //
+  let mut x = PCG32::seed(1, 2);
+  let n = x.to_bytes(&mut buf).unwrap();
+  assert_eq!(n, PCG32::STATE_BYTES);
+  assert_eq!(&buf[..4], &[b'R', b'Z', VERSION, TAG_PCG32]);
+  let mut y = PCG32::from_bytes(&buf[..n]).unwrap();
+  assert_eq!(x.next_u32(), y.next_u32());
+  //
+  let mut x = PCG32K::<3>::seed(1, [4, 5, 6]);
+  let n = x.to_bytes(&mut buf).unwrap();
+  let mut y = PCG32K::<3>::from_bytes(&buf[..n]).unwrap();
+  assert_eq!(x.next_u32(), y.next_u32());
+  //
+  let mut x = PCG64DXSM::seed(1, 2);
+  let n = x.to_bytes(&mut buf).unwrap();
+  let mut y = PCG64DXSM::from_bytes(&buf[..n]).unwrap();
+  assert_eq!(x.next_u64(), y.next_u64());
+  //
+  let mut x = MCG32::seed(3);
+  let n = x.to_bytes(&mut buf).unwrap();
+  let mut y = MCG32::from_bytes(&buf[..n]).unwrap();
+  assert_eq!(x.next_u32(), y.next_u32());
+  //
+  let mut x = MCG64::seed(3);
+  let n = x.to_bytes(&mut buf).unwrap();
+  let mut y = MCG64::from_bytes(&buf[..n]).unwrap();
+  assert_eq!(x.next_u64(), y.next_u64());
+  //
+  let mut x = Philox4x32::new([7, 8]);
+  x.next_u32();
+  let n = x.to_bytes(&mut buf).unwrap();
+  let mut y = Philox4x32::from_bytes(&buf[..n]).unwrap();
+  assert_eq!(x.next_u32(), y.next_u32());
+  //
+  let mut x = Philox4x32::with_counter([7, 8], u128::MAX - 1);
+  x.jump(6);
+  let n = x.to_bytes(&mut buf).unwrap();
+  let mut y = Philox4x32::from_bytes(&buf[..n]).unwrap();
+  assert_eq!(y.block_position(), (u128::MAX, 2));
+  assert_eq!(x.next_u32(), y.next_u32());
+}
+
+#[test]
+fn test_state_bytes_errors() {
+  let mut buf = [0_u8; 64];
+  let n = PCG32K::<3>::seed(1, [4, 5, 6]).to_bytes(&mut buf).unwrap();
+  let bytes = &buf[..n];
+  assert_eq!(
+    PCG32::from_bytes(bytes).unwrap_err(),
+    Error::BadState(StateError::WrongType { expected: TAG_PCG32, found: TAG_PCG32K })
+  );
+  assert_eq!(
+    PCG32K::<4>::from_bytes(bytes).unwrap_err(),
+    Error::BadState(StateError::WrongK { expected: 4, found: 3 })
+  );
+  assert_eq!(
+    PCG32K::<3>::from_bytes(&bytes[..n - 1]).unwrap_err(),
+    Error::BadState(StateError::Truncated { needed: n, found: n - 1 })
+  );
+  assert_eq!(
+    PCG32K::<3>::from_bytes(&buf[..n + 1]).unwrap_err(),
+    Error::BadState(StateError::TrailingBytes { needed: n, found: n + 1 })
+  );
+  assert_eq!(
+    PCG32K::<3>::from_bytes(&bytes[1..]).unwrap_err(),
+    Error::BadState(StateError::BadMagic)
+  );
+  assert_eq!(
+    PCG32::seed(1, 1).to_bytes(&mut buf[..10]).unwrap_err(),
+    Error::BadState(StateError::BufferTooSmall { needed: PCG32::STATE_BYTES, found: 10 })
+  );
+}
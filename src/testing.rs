@@ -0,0 +1,333 @@
+//! Generators with predictable output, for writing unit tests.
+//!
+//! Code that takes a `&mut impl Gen32` can be tested with these instead of a
+//! real generator, so that tests can force particular results.
+//!
+//! Keep in mind that the [Gen32] methods don't use the raw `u32` directly. For
+//! example, [d6](Gen32::d6) looks at only the high 16 bits of each value, and
+//! it will reject some values and draw again. The `raw_for` method of the
+//! bounded range types gives a raw value that maps to a particular result.
+
+use crate::{BoundedRandU16, BoundedRandU32, Gen32};
+
+/// What a [ScriptedGen] does after it has given all of its values.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ScriptEnd {
+  /// Panic on the next draw.
+  Panic,
+  /// Start over from the first value.
+  Cycle,
+}
+
+/// A "generator" that gives a fixed sequence of values.
+///
+/// ```
+/// # use randomize::{Gen32, testing::ScriptedGen};
+/// let mut g = ScriptedGen::new(&[1, 2, 3]);
+/// assert_eq!(g.next_u32(), 1);
+/// assert_eq!(g.next_u32(), 2);
+/// assert_eq!(g.remaining(), 1);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ScriptedGen<'a> {
+  values: &'a [u32],
+  pos: usize,
+  end: ScriptEnd,
+}
+impl<'a> ScriptedGen<'a> {
+  /// Makes a generator that panics once all of the values have been used.
+  #[inline]
+  #[must_use]
+  pub const fn new(values: &'a [u32]) -> Self {
+    Self { values, pos: 0, end: ScriptEnd::Panic }
+  }
+
+  /// Makes a generator that repeats the values forever.
+  ///
+  /// ## Panics
+  /// * If the slice is empty.
+  #[inline]
+  #[must_use]
+  pub const fn cycling(values: &'a [u32]) -> Self {
+    assert!(!values.is_empty(), "can't cycle an empty script");
+    Self { values, pos: 0, end: ScriptEnd::Cycle }
+  }
+
+  /// What this generator does when it runs out of values.
+  #[inline]
+  #[must_use]
+  pub const fn end(&self) -> ScriptEnd {
+    self.end
+  }
+
+  /// The index of the value the next draw will give.
+  #[inline]
+  #[must_use]
+  pub const fn position(&self) -> usize {
+    self.pos
+  }
+
+  /// The number of values left before the end of the script.
+  #[inline]
+  #[must_use]
+  pub const fn remaining(&self) -> usize {
+    self.values.len() - self.pos
+  }
+
+  /// Gives the next value of the script.
+  ///
+  /// ## Panics
+  /// * If the script is used up and the generator is not cycling.
+  #[inline]
+  pub fn next_u32(&mut self) -> u32 {
+    if self.pos == self.values.len() {
+      match self.end {
+        ScriptEnd::Panic => {
+          panic!("ScriptedGen ran out of values after {} draws", self.values.len())
+        }
+        ScriptEnd::Cycle => self.pos = 0,
+      }
+    }
+    let out = self.values[self.pos];
+    self.pos += 1;
+    out
+  }
+}
+impl Gen32 for ScriptedGen<'_> {
+  #[inline]
+  fn next_u32(&mut self) -> u32 {
+    ScriptedGen::next_u32(self)
+  }
+}
+
+/// A "generator" that always gives the same value.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[repr(transparent)]
+pub struct ConstGen(pub u32);
+impl Gen32 for ConstGen {
+  #[inline]
+  fn next_u32(&mut self) -> u32 {
+    self.0
+  }
+}
+
+/// Wraps a generator and counts how many values are drawn from it.
+///
+/// ```
+/// # use randomize::{Gen32, PCG32, testing::CountingGen};
+/// let mut g = CountingGen::new(PCG32::seed(1, 1));
+/// let _ = (g.d6(), g.d6(), g.d6());
+/// assert!(g.count() >= 3);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct CountingGen<G> {
+  gen: G,
+  count: u64,
+}
+impl<G> CountingGen<G> {
+  /// Wraps a generator, starting from a count of 0.
+  #[inline]
+  #[must_use]
+  pub const fn new(gen: G) -> Self {
+    Self { gen, count: 0 }
+  }
+
+  /// The number of values drawn so far.
+  #[inline]
+  #[must_use]
+  pub const fn count(&self) -> u64 {
+    self.count
+  }
+
+  /// Sets the count back to 0.
+  #[inline]
+  pub const fn reset_count(&mut self) {
+    self.count = 0;
+  }
+
+  /// Gets the wrapped generator.
+  #[inline]
+  #[must_use]
+  pub const fn inner(&self) -> &G {
+    &self.gen
+  }
+
+  /// Unwraps the generator.
+  #[inline]
+  #[must_use]
+  pub fn into_inner(self) -> G {
+    self.gen
+  }
+}
+impl<G: Gen32> Gen32 for CountingGen<G> {
+  #[inline]
+  fn next_u32(&mut self) -> u32 {
+    self.count += 1;
+    self.gen.next_u32()
+  }
+}
+
+/// One raw draw logged by a [DrawLog].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct Draw {
+  /// The value the generator gave.
+  pub raw: u32,
+  /// The bounded result the raw value became.
+  ///
+  /// This is `None` if the value was rejected, or if it was drawn with
+  /// `next_u32` rather than a bounded sampling method.
+  pub result: Option<u32>,
+}
+
+/// Wraps a generator and logs every draw into a buffer.
+///
+/// Bounded values drawn with [sample_u32](Gen32::sample_u32) or
+/// [sample_u16](Gen32::sample_u16), which includes the dice and the other
+/// bounded helpers of [Gen32], are logged along with the result that each raw
+/// value became, including the rejected ones. Feeding the raw values of the
+/// log to a [ScriptedGen] replays the same sequence.
+///
+/// ## Panics
+/// * Drawing a value panics if the buffer is full.
+///
+/// ```
+/// # use randomize::{Gen32, PCG32, testing::*};
+/// let mut buf = [Draw::default(); 16];
+/// let mut log = DrawLog::new(PCG32::seed(1, 1), &mut buf);
+/// let roll = log.d6();
+/// let last = log.draws().last().unwrap();
+/// assert_eq!(last.result, Some(roll as u32 - 1));
+/// ```
+#[derive(Debug)]
+pub struct DrawLog<'b, G> {
+  gen: G,
+  buf: &'b mut [Draw],
+  len: usize,
+}
+impl<'b, G: Gen32> DrawLog<'b, G> {
+  /// Wraps a generator, logging into the buffer given.
+  #[inline]
+  #[must_use]
+  pub const fn new(gen: G, buf: &'b mut [Draw]) -> Self {
+    Self { gen, buf, len: 0 }
+  }
+
+  /// The draws logged so far.
+  #[inline]
+  #[must_use]
+  pub fn draws(&self) -> &[Draw] {
+    &self.buf[..self.len]
+  }
+
+  /// Unwraps the generator.
+  #[inline]
+  #[must_use]
+  pub fn into_inner(self) -> G {
+    self.gen
+  }
+
+  fn push(&mut self, draw: Draw) {
+    assert!(self.len < self.buf.len(), "DrawLog buffer is full");
+    self.buf[self.len] = draw;
+    self.len += 1;
+  }
+}
+impl<G: Gen32> Gen32 for DrawLog<'_, G> {
+  #[inline]
+  fn next_u32(&mut self) -> u32 {
+    let raw = self.gen.next_u32();
+    self.push(Draw { raw, result: None });
+    raw
+  }
+
+  #[inline]
+  fn sample_u32(&mut self, bounded: BoundedRandU32) -> u32 {
+    loop {
+      let raw = self.gen.next_u32();
+      let result = bounded.place_in_range(raw);
+      self.push(Draw { raw, result });
+      if let Some(output) = result {
+        return output;
+      }
+    }
+  }
+
+  #[inline]
+  fn sample_u16(&mut self, bounded: BoundedRandU16) -> u16 {
+    loop {
+      let raw = self.gen.next_u32();
+      let result = bounded.place_in_range((raw >> 16) as u16);
+      self.push(Draw { raw, result: result.map(u32::from) });
+      if let Some(output) = result {
+        return output;
+      }
+    }
+  }
+}
+
+#[test]
+fn test_scripted_gen() {
+  let mut g = ScriptedGen::cycling(&[5, 6]);
+  assert_eq!([g.next_u32(), g.next_u32(), g.next_u32()], [5, 6, 5]);
+  assert_eq!(g.position(), 1);
+
+  // the high 16 bits pick the face, and a high part of 0 is rejected.
+  let mut g = ScriptedGen::new(&[0, 0x0001_0000, u32::MAX]);
+  assert_eq!(g.d6(), 1);
+  assert_eq!(g.d6(), 6);
+  assert_eq!(g.remaining(), 0);
+}
+
+#[test]
+#[should_panic = "ScriptedGen ran out of values after 1 draws"]
+fn test_scripted_gen_exhausted() {
+  let mut g = ScriptedGen::new(&[0]);
+  g.next_u32();
+  g.next_u32();
+}
+
+#[test]
+fn test_counting_gen() {
+  let mut g = CountingGen::new(ConstGen(u32::MAX));
+  assert_eq!(g.d20(), 20);
+  assert!(g.next_bool());
+  assert_eq!(g.count(), 2);
+  g.reset_count();
+  assert_eq!(g.count(), 0);
+  assert_eq!(g.into_inner(), ConstGen(u32::MAX));
+}
+
+#[test]
+fn test_draw_log_replays() {
+  let mut buf = [Draw::default(); 8];
+  let six = BoundedRandU16::_6;
+  let script = [0, u32::from(six.raw_for(2)) << 16, 7];
+  let mut log = DrawLog::new(ScriptedGen::new(&script), &mut buf);
+  assert_eq!(log.d6(), 3);
+  assert_eq!(log.next_u32(), 7);
+  let draws = log.draws();
+  assert_eq!(draws.len(), 3);
+  assert_eq!(draws[0].result, None);
+  assert_eq!(draws[1].result, Some(2));
+
+  let raws: [u32; 3] = core::array::from_fn(|i| draws[i].raw);
+  let mut replay = ScriptedGen::new(&raws);
+  assert_eq!(replay.d6(), 3);
+}
+
+#[test]
+fn test_draw_log_gen32_helpers() {
+  let mut buf = [Draw::default(); 64];
+  let script = [0xC000_0000, 0x3FFF_FFFF, 0xE000_0000];
+  let mut log = DrawLog::new(ScriptedGen::new(&script), &mut buf);
+  assert_eq!([log.d4(), log.d4(), log.d8()], [4, 1, 8]);
+  let results: [Option<u32>; 3] = core::array::from_fn(|i| log.draws()[i].result);
+  assert_eq!(results, [Some(3), Some(0), Some(7)]);
+
+  // each bounded sample ends with exactly one accepted draw.
+  let mut log = DrawLog::new(crate::PCG32::seed(1, 1), &mut buf);
+  log.d20();
+  log.shuffle(&mut [0; 5]);
+  log.next_char_in(&['a', 'b', 'c']);
+  assert_eq!(log.draws().iter().filter(|d| d.result.is_some()).count(), 1 + 4 + 1);
+}
@@ -0,0 +1,138 @@
+//! Convenience functions using a thread-local generator.
+
+use crate::{Gen32, SeedSeq, PCG32K};
+use std::cell::RefCell;
+
+/// The type of generator used by the thread-local functions.
+pub type ThreadRng = PCG32K<8>;
+
+std::thread_local! {
+  static THREAD_RNG: RefCell<Option<ThreadRng>> = const { RefCell::new(None) };
+}
+
+/// Runs a closure with this thread's generator.
+///
+/// The generator is seeded with [getrandom](getrandom::getrandom) the first
+/// time that it's used on each thread (unless
+/// [reseed_thread_rng] is called first). If `getrandom` fails, the generator
+/// is seeded with [weak entropy](crate::seed_from_weak_entropy) using the
+/// system clock instead.
+///
+/// ## Panics
+/// * If the closure tries to use the thread's generator (such as by calling
+///   [d6] or another function of this module).
+#[inline]
+pub fn with_thread_rng<R, F: FnOnce(&mut ThreadRng) -> R>(f: F) -> R {
+  THREAD_RNG.with(|cell| {
+    let mut opt = cell.borrow_mut();
+    let g = opt.get_or_insert_with(|| {
+      ThreadRng::from_getrandom().unwrap_or_else(|_| ThreadRng::from_weak_entropy(clock_nanos()))
+    });
+    f(g)
+  })
+}
+
+/// The system clock in nanoseconds since the Unix epoch, or 0 if unavailable.
+#[inline]
+fn clock_nanos() -> u64 {
+  use std::time::{SystemTime, UNIX_EPOCH};
+  SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |d| d.as_nanos() as u64)
+}
+
+/// Sets this thread's generator to a fixed seed.
+///
+/// This makes the thread's outputs reproducible, such as for tests. The
+/// generator is made with a [SeedSeq] using the `seed` as entropy.
+#[inline]
+pub fn reseed_thread_rng(seed: u64) {
+  let g = SeedSeq::new(u128::from(seed)).pcg32k();
+  THREAD_RNG.with(|cell| *cell.borrow_mut() = Some(g));
+}
+
+/// Gives a uniformly distributed value from the thread's generator.
+#[inline]
+pub fn u32() -> u32 {
+  with_thread_rng(|g| g.next_u32())
+}
+
+/// Gives a uniformly distributed value from the thread's generator.
+#[inline]
+pub fn i32() -> i32 {
+  with_thread_rng(|g| g.next_i32())
+}
+
+/// Gives a uniformly distributed value from the thread's generator.
+#[inline]
+pub fn bool() -> bool {
+  with_thread_rng(|g| g.next_bool())
+}
+
+/// Gives a value in the range `0.0 ..= 1.0` from the thread's generator.
+#[inline]
+pub fn f32_unit() -> f32 {
+  with_thread_rng(|g| g.next_f32_unit())
+}
+
+/// Gives a value in the range `1 ..= 4` from the thread's generator.
+#[inline]
+pub fn d4() -> i32 {
+  with_thread_rng(|g| g.d4())
+}
+
+/// Gives a value in the range `1 ..= 6` from the thread's generator.
+#[inline]
+pub fn d6() -> i32 {
+  with_thread_rng(|g| g.d6())
+}
+
+/// Gives a value in the range `1 ..= 8` from the thread's generator.
+#[inline]
+pub fn d8() -> i32 {
+  with_thread_rng(|g| g.d8())
+}
+
+/// Gives a value in the range `1 ..= 10` from the thread's generator.
+#[inline]
+pub fn d10() -> i32 {
+  with_thread_rng(|g| g.d10())
+}
+
+/// Gives a value in the range `1 ..= 12` from the thread's generator.
+#[inline]
+pub fn d12() -> i32 {
+  with_thread_rng(|g| g.d12())
+}
+
+/// Gives a value in the range `1 ..= 20` from the thread's generator.
+#[inline]
+pub fn d20() -> i32 {
+  with_thread_rng(|g| g.d20())
+}
+
+/// Shuffles a slice using the thread's generator.
+///
+/// ## Panics
+/// * If the slice has more than `u32::MAX` elements.
+#[inline]
+pub fn shuffle<T>(items: &mut [T]) {
+  with_thread_rng(|g| g.shuffle(items))
+}
+
+#[test]
+fn test_reseed_thread_rng() {
+  reseed_thread_rng(5);
+  let a = [u32(), u32(), u32()];
+  reseed_thread_rng(5);
+  let b = [u32(), u32(), u32()];
+  assert_eq!(a, b);
+  let mut expected = SeedSeq::new(5).pcg32k::<8>();
+  assert_eq!(a[0], expected.next_u32());
+  // other threads are not affected.
+  let other = std::thread::spawn(|| {
+    let x = with_thread_rng(|g| g.clone());
+    (x.state, x.ext)
+  })
+  .join()
+  .unwrap();
+  assert_ne!(other.1, expected.ext);
+}
@@ -0,0 +1,86 @@
+use crate::{
+  formulas::{mix64, splitmix64},
+  PCG32, PCG32K,
+};
+
+/// Makes a seed value from weak sources of entropy.
+///
+/// **This is NOT secure**, and is not even guaranteed to give different values
+/// from run to run. It's meant for when [getrandom](https://docs.rs/getrandom) isn't
+/// available (such as on embedded devices or in wasm without JS) and you'd like
+/// seeds to at least *usually* vary.
+///
+/// The following are hashed together:
+/// * The `timer` value you pass in. This should be something that changes
+///   often, such as a cycle counter, a clock, or the time between startup and
+///   the first user input. Passing a value that varies is the most important
+///   part of getting varied seeds.
+/// * The address of a stack variable and of a static (which can vary between
+///   runs when the target uses address space randomization).
+/// * A counter that goes up each time this function is called (on targets with
+///   pointer sized atomics), so calls within one run of a program give
+///   different seeds even if `timer` doesn't change.
+#[inline]
+#[must_use]
+pub fn seed_from_weak_entropy(timer: u64) -> u64 {
+  static ANCHOR: u8 = 0;
+  let stack_local = 0_u8;
+  let stack_addr = core::ptr::addr_of!(stack_local) as usize as u64;
+  let static_addr = core::ptr::addr_of!(ANCHOR) as usize as u64;
+  let count = next_count();
+  let mut h = mix64(timer);
+  h = splitmix64(h ^ stack_addr, 0);
+  h = splitmix64(h ^ static_addr, 1);
+  splitmix64(h ^ count, 2)
+}
+
+#[cfg(target_has_atomic = "ptr")]
+#[inline]
+fn next_count() -> u64 {
+  use core::sync::atomic::{AtomicUsize, Ordering};
+  static COUNTER: AtomicUsize = AtomicUsize::new(0);
+  COUNTER.fetch_add(1, Ordering::Relaxed) as u64
+}
+
+#[cfg(not(target_has_atomic = "ptr"))]
+#[inline]
+const fn next_count() -> u64 {
+  0
+}
+
+impl PCG32 {
+  /// Create a new generator seeded with [weak entropy](seed_from_weak_entropy).
+  ///
+  /// **This is NOT secure.** Use `from_getrandom` when it's available.
+  #[inline]
+  #[must_use]
+  pub fn from_weak_entropy(timer: u64) -> Self {
+    let h = seed_from_weak_entropy(timer);
+    Self::new(splitmix64(h, 0), splitmix64(h, 1) | 1)
+  }
+}
+
+impl<const K: usize> PCG32K<K> {
+  /// Create a new generator seeded with [weak entropy](seed_from_weak_entropy).
+  ///
+  /// **This is NOT secure.** Use `from_getrandom` when it's available.
+  #[inline]
+  #[must_use]
+  pub fn from_weak_entropy(timer: u64) -> Self {
+    let h = seed_from_weak_entropy(timer);
+    let ext = core::array::from_fn(|i| (splitmix64(h, 1 + i as u64) >> 32) as u32);
+    Self::new(splitmix64(h, 0), ext)
+  }
+}
+
+#[test]
+fn test_weak_entropy_varies() {
+  // Even with a fixed timer, the counter makes each call different.
+  let a = seed_from_weak_entropy(0);
+  let b = seed_from_weak_entropy(0);
+  assert_ne!(a, b);
+  let x = PCG32::from_weak_entropy(0);
+  let y = PCG32::from_weak_entropy(0);
+  assert_ne!(x.state, y.state);
+  assert_eq!(x.inc % 2, 1);
+}